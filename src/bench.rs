@@ -0,0 +1,212 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::json::Value;
+use crate::{ParseError, Solution};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts allocations made through the system allocator. Binaries install it
+/// with `#[global_allocator]`; without it every allocation count reads 0.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+pub fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+/// Timings of one stage over all iterations, and its allocations per
+/// iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub allocations: usize,
+}
+
+pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    assert!(iterations > 0);
+
+    let mut samples = Vec::with_capacity(iterations);
+    let allocations_before = allocations();
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+
+    let allocations = (allocations() - allocations_before) / iterations;
+
+    samples.sort();
+    let p95_idx = (samples.len() * 95).div_ceil(100) - 1;
+
+    Stats {
+        min: samples[0],
+        median: samples[samples.len() / 2],
+        p95: samples[p95_idx],
+        allocations,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub stage: String,
+    pub stats: Stats,
+}
+
+/// Benchmarks the parsing and both parts of a day separately.
+pub fn run<S: Solution>(input: &str, iterations: usize) -> Result<Vec<Measurement>, ParseError> {
+    let parsed = S::parse(input)?;
+    S::part1(&parsed)?;
+    S::part2(&parsed)?;
+
+    let measurement = |stage: &str, stats| Measurement {
+        stage: stage.to_string(),
+        stats,
+    };
+
+    Ok(vec![
+        measurement("parse", measure(iterations, || S::parse(black_box(input)))),
+        measurement(
+            "part1",
+            measure(iterations, || S::part1(black_box(&parsed))),
+        ),
+        measurement(
+            "part2",
+            measure(iterations, || S::part2(black_box(&parsed))),
+        ),
+    ])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub iterations: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> Value {
+        let nanos = |d: Duration| Value::from(d.as_nanos() as u64);
+
+        Value::Object(vec![
+            ("day".to_string(), self.day.into()),
+            ("iterations".to_string(), self.iterations.into()),
+            (
+                "stages".to_string(),
+                Value::Array(
+                    self.measurements
+                        .iter()
+                        .map(|m| {
+                            Value::Object(vec![
+                                ("stage".to_string(), m.stage.as_str().into()),
+                                ("min_ns".to_string(), nanos(m.stats.min)),
+                                ("median_ns".to_string(), nanos(m.stats.median)),
+                                ("p95_ns".to_string(), nanos(m.stats.p95)),
+                                ("allocations".to_string(), m.stats.allocations.into()),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let number = |v: &Value, key| v.get(key).and_then(Value::as_f64);
+        let nanos = |v: &Value, key| number(v, key).map(|n| Duration::from_nanos(n as u64));
+
+        let measurements = value
+            .get("stages")?
+            .as_array()?
+            .iter()
+            .map(|m| {
+                Some(Measurement {
+                    stage: m.get("stage")?.as_str()?.to_string(),
+                    stats: Stats {
+                        min: nanos(m, "min_ns")?,
+                        median: nanos(m, "median_ns")?,
+                        p95: nanos(m, "p95_ns")?,
+                        allocations: number(m, "allocations")? as usize,
+                    },
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            day: number(value, "day")? as u8,
+            iterations: number(value, "iterations")? as usize,
+            measurements,
+        })
+    }
+
+    /// Change of the median time of `stage` relative to `baseline`, as a
+    /// fraction (`0.1` is 10% slower).
+    pub fn change(&self, baseline: &Report, stage: &str) -> Option<f64> {
+        let median = |report: &Report| {
+            report
+                .measurements
+                .iter()
+                .find(|m| m.stage == stage)
+                .map(|m| m.stats.median.as_secs_f64())
+        };
+
+        let before = median(baseline)?;
+        let after = median(self)?;
+        if before == 0.0 {
+            return None;
+        }
+
+        Some(after / before - 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day6::Day6, json};
+
+    #[test]
+    fn report() {
+        let stats = measure(20, || vec![0u8; 16]);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+
+        let measurements = run::<Day6>("Time: 7\nDistance: 9", 5).unwrap();
+        let stages: Vec<_> = measurements.iter().map(|m| m.stage.as_str()).collect();
+        assert_eq!(stages, ["parse", "part1", "part2"]);
+
+        let mut baseline = Report {
+            day: 6,
+            iterations: 5,
+            measurements,
+        };
+        let json = json::parse(&baseline.to_json().to_string()).unwrap();
+        assert_eq!(Report::from_json(&json), Some(baseline.clone()));
+
+        let mut slower = baseline.clone();
+        baseline.measurements[0].stats.median = Duration::from_nanos(100);
+        slower.measurements[0].stats.median = Duration::from_nanos(150);
+        let change = slower.change(&baseline, "parse").unwrap();
+        assert!((change - 0.5).abs() < 1e-9);
+        assert_eq!(slower.change(&baseline, "unknown"), None);
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use aoc2023::input::{self, InputSource};
+use aoc2023::registry::{self, Day};
+
+pub struct Args {
+    args: std::vec::IntoIter<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Self {
+            args: args.into_iter(),
+        }
+    }
+
+    pub fn value(&mut self, flag: &str, expected: &str) -> Result<String, String> {
+        self.args
+            .next()
+            .ok_or_else(|| format!("{flag} expects {expected}"))
+    }
+
+    pub fn parsed<T: FromStr>(&mut self, flag: &str, expected: &str) -> Result<T, String> {
+        self.value(flag, expected)?
+            .parse()
+            .map_err(|_| format!("{flag} expects {expected}"))
+    }
+}
+
+impl Iterator for Args {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.args.next()
+    }
+}
+
+/// Options choosing where the puzzle inputs are read from.
+#[derive(Default)]
+pub struct InputArgs {
+    path: Option<String>,
+    user: Option<String>,
+    dir: Option<PathBuf>,
+}
+
+impl InputArgs {
+    /// Consumes `flag` and its value if it is an input option.
+    pub fn parse_flag(&mut self, flag: &str, args: &mut Args) -> Result<bool, String> {
+        match flag {
+            "--input" => self.path = Some(args.value(flag, "a path or -")?),
+            "--user" => self.user = Some(args.value(flag, "a name")?),
+            "--inputs-dir" => self.dir = Some(args.value(flag, "a directory")?.into()),
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    pub fn source(self, days: &[&Day]) -> Result<InputSource, String> {
+        match self.path.as_deref() {
+            Some(_) if days.len() > 1 => Err("--input needs a single day".to_string()),
+            Some("-") => Ok(InputSource::Stdin),
+            Some(path) => Ok(InputSource::File(PathBuf::from(path))),
+            None => InputSource::user(self.dir, self.user).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Reads the input of a single day.
+    pub fn load(self, day: u8) -> Result<String, String> {
+        let day = registry::find(day).ok_or_else(|| format!("No day {day}"))?;
+        let source = self.source(&[day])?;
+
+        input::load(&source, day.day).map_err(|e| format!("Missing input for day {}: {e}", day.day))
+    }
+}
+
+pub fn parse_selection(s: &str) -> Result<Vec<&'static Day>, String> {
+    let selection =
+        registry::parse_selection(s).ok_or_else(|| format!("Invalid day selection `{s}`"))?;
+
+    let days = registry::select(&selection);
+    if days.is_empty() {
+        return Err(format!(
+            "No day in {}..={}",
+            selection.start(),
+            selection.end()
+        ));
+    }
+
+    Ok(days)
+}
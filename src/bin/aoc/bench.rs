@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use aoc2023::bench::Report;
+use aoc2023::{input, json};
+
+use crate::args::{self, Args, InputArgs};
+
+const DEFAULT_ITERATIONS: usize = 100;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+fn load_baseline(path: &str) -> Result<Vec<Report>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let value = json::parse(&text).map_err(|e| format!("Invalid baseline {path}: {e}"))?;
+
+    value
+        .as_array()
+        .and_then(|reports| reports.iter().map(Report::from_json).collect())
+        .ok_or_else(|| format!("Invalid baseline {path}: unexpected layout"))
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+pub fn run(mut args: Args) -> Result<(), String> {
+    let mut days = None;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if input_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--iterations" => {
+                iterations = args.parsed("--iterations", "a positive count")?;
+                if iterations == 0 {
+                    return Err("--iterations expects a positive count".to_string());
+                }
+            }
+            "--save" => save = Some(args.value("--save", "a file")?),
+            "--baseline" => baseline = Some(args.value("--baseline", "a file")?),
+            "--threshold" => threshold = args.parsed("--threshold", "a percentage")?,
+            s if days.is_none() => days = Some(args::parse_selection(s)?),
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    let days = days.ok_or("Missing day selection")?;
+    let source = input_args.source(&days)?;
+    let baseline = baseline.as_deref().map(load_baseline).transpose()?;
+
+    let mut reports = vec![];
+    for day in days {
+        let input = input::load(&source, day.day)
+            .map_err(|e| format!("Missing input for day {}: {e}", day.day))?;
+
+        let measurements = (day.bench)(&input, iterations).map_err(|e| e.to_string())?;
+        reports.push(Report {
+            day: day.day,
+            iterations,
+            measurements,
+        });
+    }
+
+    print!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>7}",
+        "Day", "Stage", "Min", "Median", "p95", "Allocs"
+    );
+    if baseline.is_some() {
+        print!("  {:>10}  {:>8}", "Baseline", "Change");
+    }
+    println!();
+
+    let mut regressions = 0;
+    for report in &reports {
+        let previous = baseline
+            .iter()
+            .flatten()
+            .find(|previous| previous.day == report.day);
+
+        for m in &report.measurements {
+            print!(
+                "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>7}",
+                report.day,
+                m.stage,
+                format_duration(m.stats.min),
+                format_duration(m.stats.median),
+                format_duration(m.stats.p95),
+                m.stats.allocations
+            );
+
+            if baseline.is_some() {
+                let before = previous
+                    .and_then(|p| p.measurements.iter().find(|p| p.stage == m.stage))
+                    .map(|p| format_duration(p.stats.median))
+                    .unwrap_or_else(|| "-".to_string());
+
+                match previous.and_then(|p| report.change(p, &m.stage)) {
+                    Some(change) => {
+                        let change = change * 100.0;
+                        print!("  {before:>10}  {:>+7.1}%", change);
+                        if change > threshold {
+                            regressions += 1;
+                            print!("  regression");
+                        }
+                    }
+                    None => print!("  {before:>10}  {:>8}", "-"),
+                }
+            }
+            println!();
+        }
+    }
+
+    if let Some(path) = save {
+        let json = json::Value::Array(reports.iter().map(Report::to_json).collect());
+        std::fs::write(&path, format!("{json}\n"))
+            .map_err(|e| format!("Failed to write {path}: {e}"))?;
+    }
+
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} stage(s) slower than the baseline by more than {threshold}%"
+        ));
+    }
+
+    Ok(())
+}
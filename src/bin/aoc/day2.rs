@@ -0,0 +1,116 @@
+use aoc2023::day2::{self, Bag, UnknownColors};
+use aoc2023::ids::IdPolicy;
+
+use crate::args::{Args, InputArgs};
+
+fn id_policy(s: &str) -> Result<IdPolicy, String> {
+    match s {
+        "sequential" => Ok(IdPolicy::Sequential),
+        "unique" => Ok(IdPolicy::Unique),
+        "any" => Ok(IdPolicy::Any),
+        _ => Err(format!("Invalid ID policy `{s}`")),
+    }
+}
+
+fn unknown_colors(s: &str) -> Result<UnknownColors, String> {
+    match s {
+        "reject" => Ok(UnknownColors::Reject),
+        "ignore" => Ok(UnknownColors::Ignore),
+        "zero" => Ok(UnknownColors::ZeroCapacity),
+        _ => Err(format!("Invalid unknown colors policy `{s}`")),
+    }
+}
+
+fn check(mut args: Args) -> Result<(), String> {
+    let mut bag = Bag::default();
+    let mut unknown = UnknownColors::default();
+    let mut ids = IdPolicy::default();
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if input_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--bag" => bag = args.value("--bag", "cube counts")?.parse()?,
+            "--bag-file" => {
+                let path = args.value("--bag-file", "a path")?;
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {path}: {e}"))?;
+                bag = contents.parse().map_err(|e| format!("{path}: {e}"))?;
+            }
+            "--unknown-colors" => {
+                unknown =
+                    unknown_colors(&args.value("--unknown-colors", "reject, ignore or zero")?)?
+            }
+            "--ids" => ids = id_policy(&args.value("--ids", "sequential, unique or any")?)?,
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    bag.unknown = unknown;
+
+    let games = day2::parse_games(&input_args.load(2)?, ids).map_err(|e| e.to_string())?;
+
+    let mut possible = 0;
+    for game in &games.games {
+        let violations = game
+            .violations(&bag, &games.colors)
+            .map_err(|e| e.to_string())?;
+        for violation in &violations {
+            println!("{violation}");
+        }
+
+        possible += violations.is_empty() as usize;
+    }
+
+    println!(
+        "{possible} of {} games possible with {bag}, ID sum {}",
+        games.games.len(),
+        games.possible_ids_sum(&bag).map_err(|e| e.to_string())?
+    );
+
+    Ok(())
+}
+
+fn stats(mut args: Args) -> Result<(), String> {
+    let mut json = false;
+    let mut ids = IdPolicy::default();
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if input_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--format" => match args.value("--format", "csv or json")?.as_str() {
+                "csv" => json = false,
+                "json" => json = true,
+                _ => return Err("--format expects csv or json".to_string()),
+            },
+            "--ids" => ids = id_policy(&args.value("--ids", "sequential, unique or any")?)?,
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    let games = day2::parse_games(&input_args.load(2)?, ids).map_err(|e| e.to_string())?;
+    let stats = games.stats();
+
+    if json {
+        println!("{}", stats.to_json());
+    } else {
+        println!("{}", stats.to_csv());
+    }
+
+    Ok(())
+}
+
+pub fn run(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        Some("check") => check(args),
+        Some("stats") => stats(args),
+        _ => Err(crate::USAGE.to_string()),
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::Write;
+
+use aoc2023::day3::{self, GearRule, Item, ItemType, RaggedRows, Schematic, Symbols};
+
+use crate::args::{Args, InputArgs};
+
+/// Options shared by the day 3 commands: the input, how to read it and
+/// which symbols are gears.
+#[derive(Default)]
+struct SchematicArgs {
+    input: InputArgs,
+    ragged: RaggedRows,
+    rule: GearRule,
+}
+
+impl SchematicArgs {
+    /// Consumes `flag` and its value if it is a shared option.
+    fn parse_flag(&mut self, flag: &str, args: &mut Args) -> Result<bool, String> {
+        match flag {
+            "--pad-ragged" => self.ragged = RaggedRows::Pad,
+            "--gear-symbols" => self.rule.symbols = args.value(flag, "symbols or any")?.parse()?,
+            "--arity" => self.rule.arity = args.value(flag, "N or N+")?.parse()?,
+            _ => return self.input.parse_flag(flag, args),
+        }
+
+        Ok(true)
+    }
+
+    fn load(self) -> Result<(Schematic, GearRule), String> {
+        let schematic = day3::parse_schematic_with(&self.input.load(3)?, self.ragged)
+            .map_err(|e| e.to_string())?;
+
+        Ok((schematic, self.rule))
+    }
+}
+
+fn render(mut args: Args) -> Result<(), String> {
+    let mut format = "ansi".to_string();
+    let mut output = None;
+    let mut scale = 4;
+    let mut schematic_args = SchematicArgs::default();
+
+    while let Some(arg) = args.next() {
+        if schematic_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--format" => format = args.value("--format", "ansi, ppm or svg")?,
+            "--output" => output = Some(args.value("--output", "a path")?),
+            "--scale" => scale = args.parsed("--scale", "a number of pixels")?,
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    let (schematic, rule) = schematic_args.load()?;
+    let rendered = match format.as_str() {
+        "ansi" => schematic.render_ansi(&rule).into_bytes(),
+        "ppm" => schematic.render_ppm(&rule, scale),
+        "svg" => schematic.render_svg(&rule).into_bytes(),
+        _ => return Err("--format expects ansi, ppm or svg".to_string()),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered).map_err(|e| format!("Failed to write {path}: {e}"))
+        }
+        None => std::io::stdout()
+            .write_all(&rendered)
+            .map_err(|e| format!("Failed to write the image: {e}")),
+    }
+}
+
+fn query(mut args: Args) -> Result<(), String> {
+    let mut symbols = Symbols::Any;
+    let mut schematic_args = SchematicArgs::default();
+
+    while let Some(arg) = args.next() {
+        if schematic_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--symbols" => symbols = args.value("--symbols", "symbols or any")?.parse()?,
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    let (schematic, rule) = schematic_args.load()?;
+    let numbers = |parts: &[&Item]| -> Vec<String> {
+        parts
+            .iter()
+            .filter_map(|p| p.part_number())
+            .map(|n| n.to_string())
+            .collect()
+    };
+
+    println!(
+        "Parts next to `{symbols}` symbols: {}, sum {}",
+        schematic.parts_next_to(&symbols).count(),
+        schematic.sum_parts_next_to(&symbols)
+    );
+
+    let gears: Vec<_> = schematic.gears(&rule).collect();
+    for (gear, parts) in &gears {
+        println!(
+            "Gear {} at {},{}: {}",
+            gear.symbol().unwrap(),
+            gear.start.x,
+            gear.start.y,
+            numbers(parts).join(" ")
+        );
+    }
+    let ratio_sum = schematic.gear_ratio_sum(&rule).map_err(|gear| {
+        format!(
+            "Gear ratio sum overflows at gear {},{}",
+            gear.start.x, gear.start.y
+        )
+    })?;
+    println!(
+        "{} `{}` gears with {} parts, ratio sum {ratio_sum}",
+        gears.len(),
+        rule.symbols,
+        rule.arity,
+    );
+
+    let shared: Vec<_> = schematic.shared_parts().collect();
+    println!(
+        "Parts next to more than one symbol: {}",
+        numbers(&shared).join(" ")
+    );
+
+    Ok(())
+}
+
+fn clusters(mut args: Args) -> Result<(), String> {
+    let mut schematic_args = SchematicArgs::default();
+
+    while let Some(arg) = args.next() {
+        if !schematic_args.parse_flag(&arg, &mut args)? {
+            return Err(format!("Unexpected argument `{arg}`"));
+        }
+    }
+
+    let (schematic, _) = schematic_args.load()?;
+    for cluster in schematic.clusters() {
+        let items: Vec<String> = cluster
+            .items
+            .iter()
+            .map(|item| match item.type_ {
+                ItemType::Part(number) => number.to_string(),
+                ItemType::Symbol(c) => c.to_string(),
+            })
+            .collect();
+
+        println!(
+            "{},{}..{},{}: {} (sum {})",
+            cluster.min.x,
+            cluster.min.y,
+            cluster.max.x,
+            cluster.max.y,
+            items.join(" "),
+            cluster.part_sum()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn run(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        Some("render") => render(args),
+        Some("query") => query(args),
+        Some("clusters") => clusters(args),
+        _ => Err(crate::USAGE.to_string()),
+    }
+}
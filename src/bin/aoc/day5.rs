@@ -0,0 +1,84 @@
+use aoc2023::day5::{Almanac, Day5};
+use aoc2023::Solution;
+
+use crate::args::{Args, InputArgs};
+
+fn load_almanac(input_args: InputArgs) -> Result<Almanac, String> {
+    Day5::parse(&input_args.load(5)?).map_err(|e| e.to_string())
+}
+
+/// Reads `<CATEGORY> <VALUE> <CATEGORY> [INPUT OPTIONS]`.
+fn query_args(mut args: Args) -> Result<(String, usize, String, InputArgs), String> {
+    let mut positional = vec![];
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if !input_args.parse_flag(&arg, &mut args)? {
+            positional.push(arg);
+        }
+    }
+
+    let [from, value, to] = <[String; 3]>::try_from(positional)
+        .map_err(|_| "Expected `<CATEGORY> <VALUE> <CATEGORY>`".to_string())?;
+    let value = value
+        .parse()
+        .map_err(|_| format!("Invalid value `{value}`"))?;
+
+    Ok((from, value, to, input_args))
+}
+
+fn convert(args: Args) -> Result<(), String> {
+    let (from, value, to, input_args) = query_args(args)?;
+
+    let almanac = load_almanac(input_args)?;
+    let converted = almanac
+        .convert(&from, value, &to)
+        .map_err(|e| e.to_string())?;
+    println!("{from} {value} -> {to} {converted}");
+
+    Ok(())
+}
+
+fn unconvert(args: Args) -> Result<(), String> {
+    let (to, value, from, input_args) = query_args(args)?;
+
+    let almanac = load_almanac(input_args)?;
+    let sources = almanac
+        .unconvert(&to, value, &from)
+        .map_err(|e| e.to_string())?;
+    for source in sources {
+        println!("{from} {source} -> {to} {value}");
+    }
+
+    Ok(())
+}
+
+fn compose(mut args: Args) -> Result<(), String> {
+    let mut categories = vec![];
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if !input_args.parse_flag(&arg, &mut args)? {
+            categories.push(arg);
+        }
+    }
+
+    let almanac = load_almanac(input_args)?;
+    let map = match &categories[..] {
+        [] => almanac.pipeline(),
+        [from, to] => almanac.compose(from, to).map_err(|e| e.to_string())?,
+        _ => return Err("Expected `[<CATEGORY> <CATEGORY>]`".to_string()),
+    };
+    println!("{map}");
+
+    Ok(())
+}
+
+pub fn run(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        Some("convert") => convert(args),
+        Some("unconvert") => unconvert(args),
+        Some("compose") => compose(args),
+        _ => Err(crate::USAGE.to_string()),
+    }
+}
@@ -0,0 +1,81 @@
+use std::process::ExitCode;
+
+use aoc2023::bench::CountingAlloc;
+
+mod args;
+mod bench;
+mod day2;
+mod day3;
+mod day5;
+mod run;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const USAGE: &str = "Usage:
+    aoc run <DAYS> [--part 1|2] [--variant NAME] [INPUT OPTIONS]
+    aoc bench <DAYS> [--iterations N] [--save FILE] [--baseline FILE]
+                     [--threshold PERCENT] [INPUT OPTIONS]
+    aoc day2 check [--bag COUNTS | --bag-file FILE] [--unknown-colors POLICY]
+                   [--ids POLICY] [INPUT OPTIONS]
+    aoc day2 stats [--format csv|json] [--ids POLICY] [INPUT OPTIONS]
+    aoc day3 render [--format ansi|ppm|svg] [--output FILE] [--scale PIXELS]
+                    [SCHEMATIC OPTIONS] [INPUT OPTIONS]
+    aoc day3 query [--symbols SET] [SCHEMATIC OPTIONS] [INPUT OPTIONS]
+    aoc day3 clusters [SCHEMATIC OPTIONS] [INPUT OPTIONS]
+    aoc day5 convert <CATEGORY> <VALUE> <CATEGORY> [INPUT OPTIONS]
+    aoc day5 unconvert <CATEGORY> <VALUE> <CATEGORY> [INPUT OPTIONS]
+    aoc day5 compose [<CATEGORY> <CATEGORY>] [INPUT OPTIONS]
+
+DAYS is `all`, a day (`5`) or a range (`1..4`, `1..=4`).
+--variant runs an alternative algorithm instead, e.g. `brute-force` or
+`reverse-search` for day 5.
+`day2 check` lists the draws exceeding the bag, `12 red, 13 green, 14 blue` by
+default. A bag file lists the same counts, one colour per line if wanted.
+--unknown-colors treats colours missing from the bag as an error (`reject`),
+skips them (`ignore`) or counts them as absent from the bag (`zero`, default).
+`day2 stats` exports the minimum bag, cubes drawn, power and most constraining
+set of every game, and their distributions in JSON.
+--ids accepts `sequential` game IDs (default), `unique` ones or `any`.
+`day3 render` draws the schematic with valid parts in green, orphan numbers in
+red, gears in magenta and their parts in yellow.
+`day3 query` sums the parts next to symbols of SET (`any` by default, or the
+symbols written together, e.g. `*#`), and lists the gears and the parts next to
+more than one symbol.
+`day3 clusters` lists the groups of adjacent parts and symbols with their
+bounding box and the sum of their parts.
+`day5 convert` maps a value along the almanac, e.g. `seed 79 fertilizer`, and
+`day5 unconvert` lists the values mapped to it, e.g. `location 82 seed`.
+`day5 compose` prints the maps between two categories as a single one, from
+seeds to locations by default.
+
+Schematic options:
+    --pad-ragged         Pad rows shorter than the longest with `.`
+    --gear-symbols <SET> Symbols that can be gears (default: *)
+    --arity <N|N+>       Parts a gear touches, exactly N or at least N (default: 2)
+
+Input options:
+    --input <FILE|->     Read the input from a file or stdin (single day only)
+    --user <NAME>        Read inputs/<NAME>/dayNN.txt (default: $AOC_USER, $USER)
+    --inputs-dir <DIR>   Look up per-user inputs in DIR (default: $AOC_INPUTS, inputs)";
+
+fn main() -> ExitCode {
+    let mut args = args::Args::new(std::env::args().skip(1).collect());
+
+    let result = match args.next().as_deref() {
+        Some("run") => run::run(args),
+        Some("bench") => bench::run(args),
+        Some("day2") => day2::run(args),
+        Some("day3") => day3::run(args),
+        Some("day5") => day5::run(args),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use aoc2023::input;
+
+use crate::args::{self, Args, InputArgs};
+
+struct Row {
+    day: u8,
+    part: String,
+    answer: String,
+    elapsed: Duration,
+}
+
+fn print_table(rows: &[Row]) {
+    let part_width = rows
+        .iter()
+        .map(|r| r.part.len())
+        .chain(["Part".len()])
+        .max()
+        .unwrap();
+    let answer_width = rows
+        .iter()
+        .map(|r| r.answer.len())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap();
+
+    println!(
+        "{:>3}  {:>part_width$}  {:<answer_width$}  {:>10}",
+        "Day", "Part", "Answer", "Time"
+    );
+    for r in rows {
+        println!(
+            "{:>3}  {:>part_width$}  {:<answer_width$}  {:>10}",
+            r.day,
+            r.part,
+            r.answer,
+            format!("{:.2?}", r.elapsed)
+        );
+    }
+}
+
+pub fn run(mut args: Args) -> Result<(), String> {
+    let mut days = None;
+    let mut parts = vec![1, 2];
+    let mut variant = None;
+    let mut input_args = InputArgs::default();
+
+    while let Some(arg) = args.next() {
+        if input_args.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--part" => {
+                let part = args.parsed("--part", "1 or 2")?;
+                if ![1, 2].contains(&part) {
+                    return Err("--part expects 1 or 2".to_string());
+                }
+                parts = vec![part];
+            }
+            "--variant" => variant = Some(args.value("--variant", "a name")?),
+            s if days.is_none() => days = Some(args::parse_selection(s)?),
+            s => return Err(format!("Unexpected argument `{s}`")),
+        }
+    }
+
+    let days = days.ok_or("Missing day selection")?;
+    let source = input_args.source(&days)?;
+
+    let mut rows = vec![];
+    for day in days {
+        let input = input::load(&source, day.day)
+            .map_err(|e| format!("Missing input for day {}: {e}", day.day))?;
+
+        for &part in &parts {
+            let (solve, label) = match &variant {
+                Some(name) => match day.variant(name, part) {
+                    Some(solve) => (solve, format!("{part} {name}")),
+                    None => continue,
+                },
+                None => (day.part(part).unwrap(), part.to_string()),
+            };
+
+            let start = Instant::now();
+            let answer = solve(&input).map_err(|e| e.to_string())?;
+            let elapsed = start.elapsed();
+
+            rows.push(Row {
+                day: day.day,
+                part: label,
+                answer,
+                elapsed,
+            });
+        }
+    }
+
+    if let (Some(name), true) = (&variant, rows.is_empty()) {
+        return Err(format!("No selected day has a `{name}` variant"));
+    }
+
+    print_table(&rows);
+
+    Ok(())
+}
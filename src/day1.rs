@@ -0,0 +1,166 @@
+use crate::{input, ParseError, Solution};
+
+const DAY: u8 = 1;
+
+const DIGIT_MAP: &[(&str, u32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A line of the document, with its index in the input for error reporting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub idx: usize,
+    pub text: String,
+}
+
+impl Line {
+    fn value(&self, digits: impl Fn(&str) -> Option<u32>) -> Option<u32> {
+        let mut found = self
+            .text
+            .char_indices()
+            .filter_map(|(i, _)| digits(&self.text[i..]));
+        let first = found.next()?;
+
+        Some(first * 10 + found.last().unwrap_or(first))
+    }
+}
+
+/// The digit `s` starts with.
+fn digit(s: &str) -> Option<u32> {
+    s.chars().next()?.to_digit(10)
+}
+
+/// The digit `s` starts with, spelled out or not.
+fn spelled_digit(s: &str) -> Option<u32> {
+    digit(s).or_else(|| {
+        DIGIT_MAP
+            .iter()
+            .find(|(digit_str, _)| s.starts_with(digit_str))
+            .map(|&(_, digit)| digit)
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    input::lines(input)
+        .map(|(idx, s)| {
+            let line = Line {
+                idx,
+                text: s.to_string(),
+            };
+
+            // Any line with a digit has a value for part 2
+            if line.value(spelled_digit).is_none() {
+                return Err(ParseError::new(DAY, idx, s, s, "No digit in line"));
+            }
+
+            Ok(line)
+        })
+        .collect()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Line>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    /// Fails on lines with spelled-out digits only.
+    fn part1(lines: &Self::Input) -> Result<u32, ParseError> {
+        lines
+            .iter()
+            .map(|line| {
+                line.value(digit).ok_or_else(|| {
+                    let text = line.text.as_str();
+                    ParseError::new(DAY, line.idx, text, text, "No numeric digit in line")
+                })
+            })
+            .sum()
+    }
+
+    fn part2(lines: &Self::Input) -> Result<u32, ParseError> {
+        Ok(lines
+            .iter()
+            .filter_map(|line| line.value(spelled_digit))
+            .sum())
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    Day1::part1(&Day1::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u32, ParseError> {
+    Day1::part2(&Day1::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const TEST_INPUT2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn day1() {
+        assert_eq!(solve_part1(TEST_INPUT1), Ok(142));
+
+        assert_eq!(solve_part2(TEST_INPUT2), Ok(281));
+
+        if let Some(input) = crate::input::reference(1) {
+            assert_eq!(solve_part1(&input), Ok(55130));
+            assert_eq!(solve_part2(&input), Ok(54985));
+        }
+    }
+
+    #[test]
+    fn digits() {
+        let e = solve_part1("1\neightwothree").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "eightwothree"));
+        assert_eq!(e.message, "No numeric digit in line");
+        assert_eq!(solve_part2("1\neightwothree"), Ok(11 + 83));
+
+        // Digits are found by character, not by byte
+        assert_eq!(solve_part1("1é\né2ñ"), Ok(11 + 22));
+        assert_eq!(solve_part2("éone2\nñtwoé"), Ok(12 + 22));
+        assert_eq!(solve_part2("oneight"), Ok(18));
+
+        assert_eq!(
+            solve_part2("abc\n1").unwrap_err().message,
+            "No digit in line"
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT1) {
+            assert_eq!(solve_part1(&input), Ok(142));
+        }
+
+        for input in crate::input::encodings(TEST_INPUT2) {
+            assert_eq!(solve_part2(&input), Ok(281));
+        }
+    }
+}
@@ -0,0 +1,846 @@
+use std::cmp::max;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ids::{IdPolicy, Ids};
+use crate::input;
+use crate::json::Value;
+use crate::{ParseError, Solution};
+
+const DAY: u8 = 2;
+
+/// A colour interned in `Colors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Color(usize);
+
+/// The colours of an input, in order of first appearance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Colors {
+    names: Vec<String>,
+}
+
+impl Colors {
+    pub fn intern(&mut self, name: &str) -> Color {
+        match self.get(name) {
+            Some(color) => color,
+            None => {
+                self.names.push(name.to_string());
+                Color(self.names.len() - 1)
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.names.iter().position(|n| n == name).map(Color)
+    }
+
+    pub fn name(&self, color: Color) -> &str {
+        &self.names[color.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Color> {
+        (0..self.names.len()).map(Color)
+    }
+}
+
+#[derive(Debug)]
+pub struct Cubes {
+    pub color: Color,
+    pub count: u32,
+}
+
+/// How draws of colours missing from a `Bag` are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownColors {
+    /// The check fails with an `UnknownColor` error.
+    Reject,
+    /// The draws are skipped.
+    Ignore,
+    /// The bag holds none of them, as in the puzzle's "only 12 red cubes, 13
+    /// green cubes, and 14 blue cubes".
+    #[default]
+    ZeroCapacity,
+}
+
+/// How many cubes of each colour the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    capacities: Vec<(String, u32)>,
+    pub unknown: UnknownColors,
+}
+
+impl Bag {
+    pub fn new<'a>(capacities: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self {
+            capacities: capacities
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+            unknown: UnknownColors::default(),
+        }
+    }
+
+    pub fn capacities(&self) -> &[(String, u32)] {
+        &self.capacities
+    }
+
+    pub fn capacity(&self, name: &str) -> Option<u32> {
+        self.capacities
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, count)| count)
+    }
+
+    /// The product of the capacities of all colours, `None` if it overflows.
+    pub fn power(&self) -> Option<u64> {
+        self.capacities
+            .iter()
+            .try_fold(1u64, |power, &(_, count)| power.checked_mul(count.into()))
+    }
+}
+
+/// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+impl Default for Bag {
+    fn default() -> Self {
+        Self::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+}
+
+/// Reads `12 red, 13 green, 14 blue`, with commas or newlines between
+/// colours and `#` comments, so that a bag can be kept in a file.
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut bag = Bag::new([]);
+
+        let items = s
+            .lines()
+            .map(|l| l.split_once('#').map_or(l, |(l, _)| l))
+            .flat_map(|l| l.split(','))
+            .map(str::trim)
+            .filter(|item| !item.is_empty());
+        for item in items {
+            let Some((count, name)) = item.split_once(' ') else {
+                return Err(format!("Expected `<count> <color>`, got `{item}`"));
+            };
+            let count = count
+                .parse()
+                .map_err(|_| format!("Invalid cube count `{count}`"))?;
+            let name = name.trim();
+            if !is_color_name(name) {
+                return Err(format!("Unexpected color `{name}`"));
+            }
+
+            if bag.capacity(name).is_some() {
+                return Err(format!("{name} is listed twice"));
+            }
+            bag.capacities.push((name.to_string(), count));
+        }
+
+        Ok(bag)
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, (name, count)) in self.capacities.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of cubes drawn with more cubes of a colour than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub game: u32,
+    /// 0-based index of the set in the game.
+    pub set: usize,
+    pub color: String,
+    pub count: u32,
+    pub capacity: u32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Game {}, set {}: {} {} drawn, the bag holds {}",
+            self.game,
+            self.set + 1,
+            self.count,
+            self.color,
+            self.capacity
+        )
+    }
+}
+
+/// A colour drawn that the bag doesn't list, with `UnknownColors::Reject`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownColor {
+    pub game: u32,
+    pub set: usize,
+    pub color: String,
+}
+
+impl fmt::Display for UnknownColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Game {}, set {}: the bag holds no {} cubes",
+            self.game,
+            self.set + 1,
+            self.color
+        )
+    }
+}
+
+impl std::error::Error for UnknownColor {}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Vec<Cubes>>,
+    /// 0-based index of the line of the game, for error reporting.
+    pub line_idx: usize,
+    pub line: String,
+}
+
+impl Game {
+    /// Every colour of every set drawn beyond what `bag` holds.
+    pub fn violations(&self, bag: &Bag, colors: &Colors) -> Result<Vec<Violation>, UnknownColor> {
+        let mut violations = vec![];
+
+        for (set_idx, set) in self.sets.iter().enumerate() {
+            let mut drawn: Vec<Color> = set.iter().map(|c| c.color).collect();
+            drawn.sort_unstable();
+            drawn.dedup();
+
+            for color in drawn {
+                let name = colors.name(color);
+                let count = set
+                    .iter()
+                    .filter(|c| c.color == color)
+                    .map(|c| c.count)
+                    .sum();
+
+                let capacity = match (bag.capacity(name), bag.unknown) {
+                    (Some(capacity), _) => capacity,
+                    (None, UnknownColors::Reject) => {
+                        return Err(UnknownColor {
+                            game: self.id,
+                            set: set_idx,
+                            color: name.to_string(),
+                        })
+                    }
+                    (None, UnknownColors::Ignore) => continue,
+                    (None, UnknownColors::ZeroCapacity) => 0,
+                };
+
+                if count > capacity {
+                    violations.push(Violation {
+                        game: self.id,
+                        set: set_idx,
+                        color: name.to_string(),
+                        count,
+                        capacity,
+                    });
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    pub fn is_possible(&self, bag: &Bag, colors: &Colors) -> Result<bool, UnknownColor> {
+        Ok(self.violations(bag, colors)?.is_empty())
+    }
+
+    /// The fewest cubes of every colour of `colors` the game could be played
+    /// with, none for the colours it never draws.
+    pub fn minimum_bag(&self, colors: &Colors) -> Bag {
+        let mut minimum = vec![0; colors.len()];
+        for cube in self.sets.iter().flatten() {
+            minimum[cube.color.0] = max(minimum[cube.color.0], cube.count);
+        }
+
+        Bag::new(colors.iter().map(|c| (colors.name(c), minimum[c.0])))
+    }
+
+    /// Cubes drawn in each set, indexed by colour, saturating at `u32::MAX`.
+    fn set_counts(&self, colors: &Colors) -> Vec<Vec<u32>> {
+        self.sets
+            .iter()
+            .map(|set| {
+                let mut counts = vec![0u32; colors.len()];
+                for cube in set {
+                    let count = &mut counts[cube.color.0];
+                    *count = count.saturating_add(cube.count);
+                }
+
+                counts
+            })
+            .collect()
+    }
+
+    pub fn stats(&self, colors: &Colors) -> GameStats {
+        let minimum = self.minimum_bag(colors);
+        let set_counts = self.set_counts(colors);
+
+        let mut drawn = vec![0u32; colors.len()];
+        for counts in &set_counts {
+            for (total, count) in drawn.iter_mut().zip(counts) {
+                *total = total.saturating_add(*count);
+            }
+        }
+
+        // The set reaching the minimum for the most colours, then drawing the
+        // most cubes
+        let constraining_set = set_counts
+            .iter()
+            .enumerate()
+            .max_by_key(|&(idx, counts)| {
+                let reached = minimum
+                    .capacities()
+                    .iter()
+                    .zip(counts)
+                    .filter(|&(&(_, min), &count)| count > 0 && count >= min)
+                    .count();
+                (
+                    reached,
+                    counts.iter().map(|&c| u64::from(c)).sum::<u64>(),
+                    std::cmp::Reverse(idx),
+                )
+            })
+            .map(|(idx, _)| idx);
+
+        GameStats {
+            id: self.id,
+            sets: self.sets.len(),
+            power: minimum.power(),
+            minimum,
+            drawn: Bag::new(colors.iter().map(|c| (colors.name(c), drawn[c.0]))),
+            constraining_set,
+        }
+    }
+}
+
+/// A summary of one game, to explain its answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    pub id: u32,
+    pub sets: usize,
+    pub minimum: Bag,
+    /// Cubes of each colour drawn over all sets.
+    pub drawn: Bag,
+    /// 0-based index of the set reaching the minimum bag for the most
+    /// colours, `None` without sets.
+    pub constraining_set: Option<usize>,
+    /// `None` if it overflows.
+    pub power: Option<u64>,
+}
+
+/// How a value is spread over the games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: u64,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<u64>) -> Option<Self> {
+        values.sort_unstable();
+
+        Some(Self {
+            min: *values.first()?,
+            max: *values.last()?,
+            mean: values.iter().map(|&v| v as f64).sum::<f64>() / values.len() as f64,
+            median: values[values.len() / 2],
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("min".to_string(), self.min.into()),
+            ("max".to_string(), self.max.into()),
+            ("mean".to_string(), self.mean.into()),
+            ("median".to_string(), self.median.into()),
+        ])
+    }
+}
+
+/// The statistics of all the games of an input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub colors: Vec<String>,
+    pub games: Vec<GameStats>,
+}
+
+impl Stats {
+    /// Spread of the minimum bag of the games for `color`.
+    pub fn minimum_distribution(&self, color: &str) -> Option<Distribution> {
+        Distribution::new(
+            self.games
+                .iter()
+                .map(|g| g.minimum.capacity(color).map(u64::from))
+                .collect::<Option<_>>()?,
+        )
+    }
+
+    /// Spread of the powers of the games, `None` if one overflows.
+    pub fn power_distribution(&self) -> Option<Distribution> {
+        Distribution::new(self.games.iter().map(|g| g.power).collect::<Option<_>>()?)
+    }
+
+    /// One line per game, sets numbered from 1.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec!["id".to_string(), "sets".to_string()];
+        header.extend(self.colors.iter().map(|c| format!("min_{c}")));
+        header.extend(self.colors.iter().map(|c| format!("drawn_{c}")));
+        header.extend(["power".to_string(), "constraining_set".to_string()]);
+
+        let mut csv = header.join(",");
+        for game in &self.games {
+            let mut row = vec![game.id.to_string(), game.sets.to_string()];
+            row.extend(game.minimum.capacities().iter().map(|(_, n)| n.to_string()));
+            row.extend(game.drawn.capacities().iter().map(|(_, n)| n.to_string()));
+            row.push(game.power.map_or(String::new(), |p| p.to_string()));
+            row.push(
+                game.constraining_set
+                    .map_or(String::new(), |s| (s + 1).to_string()),
+            );
+
+            csv.push('\n');
+            csv.push_str(&row.join(","));
+        }
+
+        csv
+    }
+
+    /// The games, sets numbered from 1, and the distributions over them.
+    pub fn to_json(&self) -> Value {
+        let bag = |bag: &Bag| {
+            Value::Object(
+                bag.capacities()
+                    .iter()
+                    .map(|(name, count)| (name.clone(), (*count).into()))
+                    .collect(),
+            )
+        };
+        let distribution = |d: Option<Distribution>| d.map_or(Value::Null, |d| d.to_json());
+
+        let games = self
+            .games
+            .iter()
+            .map(|g| {
+                Value::Object(vec![
+                    ("id".to_string(), g.id.into()),
+                    ("sets".to_string(), g.sets.into()),
+                    ("minimum".to_string(), bag(&g.minimum)),
+                    ("drawn".to_string(), bag(&g.drawn)),
+                    (
+                        "power".to_string(),
+                        g.power.map_or(Value::Null, Value::from),
+                    ),
+                    (
+                        "constraining_set".to_string(),
+                        g.constraining_set.map_or(Value::Null, |s| (s + 1).into()),
+                    ),
+                ])
+            })
+            .collect();
+
+        Value::Object(vec![
+            (
+                "colors".to_string(),
+                Value::Array(self.colors.iter().map(|c| c.as_str().into()).collect()),
+            ),
+            ("games".to_string(), Value::Array(games)),
+            (
+                "distributions".to_string(),
+                Value::Object(vec![
+                    (
+                        "minimum".to_string(),
+                        Value::Object(
+                            self.colors
+                                .iter()
+                                .map(|c| (c.clone(), distribution(self.minimum_distribution(c))))
+                                .collect(),
+                        ),
+                    ),
+                    ("power".to_string(), distribution(self.power_distribution())),
+                ]),
+            ),
+        ])
+    }
+}
+
+/// The games of an input and the colours they draw.
+#[derive(Debug)]
+pub struct Games {
+    pub colors: Colors,
+    pub games: Vec<Game>,
+}
+
+impl Games {
+    /// Sum of the IDs of the games possible with `bag`.
+    pub fn possible_ids_sum(&self, bag: &Bag) -> Result<u32, UnknownColor> {
+        let mut sum = 0;
+        for game in &self.games {
+            if game.is_possible(bag, &self.colors)? {
+                sum += game.id;
+            }
+        }
+
+        Ok(sum)
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            colors: self
+                .colors
+                .iter()
+                .map(|c| self.colors.name(c).to_string())
+                .collect(),
+            games: self.games.iter().map(|g| g.stats(&self.colors)).collect(),
+        }
+    }
+}
+
+fn is_color_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphabetic())
+}
+
+fn parse_cubes(
+    line_idx: usize,
+    line: &str,
+    cubes: &str,
+    colors: &mut Colors,
+) -> Result<Cubes, ParseError> {
+    let error = |token, message| ParseError::new(DAY, line_idx, line, token, message);
+
+    let Some((count, color)) = cubes.split_once(' ') else {
+        return Err(error(cubes, "Expected `<count> <color>`"));
+    };
+
+    let count: u32 = count
+        .parse()
+        .map_err(|_| error(count, "Invalid cube count"))?;
+
+    if !is_color_name(color) {
+        return Err(error(color, "Unexpected color"));
+    }
+
+    Ok(Cubes {
+        color: colors.intern(color),
+        count,
+    })
+}
+
+fn parse_input(input: &str) -> Result<Games, ParseError> {
+    parse_games(input, IdPolicy::default())
+}
+
+/// Parses the games, accepting the IDs allowed by `ids`.
+pub fn parse_games(input: &str, ids: IdPolicy) -> Result<Games, ParseError> {
+    let mut games = vec![];
+    let mut colors = Colors::default();
+    let mut ids = Ids::new(DAY, "Game", ids);
+
+    for (line_idx, line) in input::lines(input) {
+        let (id, l) = ids.header(line_idx, line)?;
+
+        let sets = l
+            .split("; ")
+            .map(|s| {
+                s.split(", ")
+                    .map(|s| parse_cubes(line_idx, line, s, &mut colors))
+                    .collect::<Result<Vec<Cubes>, _>>()
+            })
+            .collect::<Result<_, _>>()?;
+
+        games.push(Game {
+            id,
+            sets,
+            line_idx,
+            line: line.to_string(),
+        });
+    }
+
+    Ok(Games { colors, games })
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Games;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(games: &Self::Input) -> Result<u32, ParseError> {
+        // The default bag counts unknown colours as missing, never rejects them
+        Ok(games.possible_ids_sum(&Bag::default()).unwrap())
+    }
+
+    /// Sums the powers of the minimum bags, over all colours of the input.
+    fn part2(games: &Self::Input) -> Result<u64, ParseError> {
+        let mut sum = 0u64;
+        for game in &games.games {
+            let error =
+                |message| ParseError::new(DAY, game.line_idx, &game.line, &game.line, message);
+
+            let power = game
+                .minimum_bag(&games.colors)
+                .power()
+                .ok_or_else(|| error("Power of the minimum bag overflows"))?;
+            sum = sum
+                .checked_add(power)
+                .ok_or_else(|| error("Sum of the powers overflows"))?;
+        }
+
+        Ok(sum)
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    Day2::part1(&Day2::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    Day2::part2(&Day2::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn day2() {
+        assert_eq!(solve_part1(TEST_INPUT), Ok(8));
+
+        assert_eq!(solve_part2(TEST_INPUT), Ok(2286));
+
+        if let Some(input) = crate::input::reference(2) {
+            assert_eq!(solve_part1(&input), Ok(2476));
+            assert_eq!(solve_part2(&input), Ok(54911));
+        }
+    }
+
+    #[test]
+    fn game_ids() {
+        let error = |input| Day2::parse(input).unwrap_err();
+
+        let e = error("Game 1: 1 red\nGame 3: 1 red");
+        assert_eq!((e.line, e.text.as_str()), (2, "3"));
+        assert_eq!(e.message, "Expected Game 2");
+        assert_eq!(
+            error("Game 1: 1 red\nGame 1: 1 red").message,
+            "Expected Game 2"
+        );
+        assert_eq!(error("Game x: 1 red").message, "Invalid ID");
+        assert_eq!(error("Gam 1: 1 red").message, "Expected `Game <id>: `");
+
+        // A reordered subset keeps the IDs of the whole input
+        let lines: Vec<_> = TEST_INPUT.lines().collect();
+        let subset = [lines[4], lines[2], lines[1]].join("\n");
+        let games = parse_games(&subset, IdPolicy::Unique).unwrap();
+        let ids: Vec<_> = games.games.iter().map(|g| g.id).collect();
+        assert_eq!(ids, [5, 3, 2]);
+        assert_eq!(Day2::part1(&games), Ok(7));
+
+        let duplicated = [lines[0], lines[0]].join("\n");
+        let e = parse_games(&duplicated, IdPolicy::Unique).unwrap_err();
+        assert_eq!(e.message, "Duplicate Game 1");
+        assert_eq!(
+            Day2::part1(&parse_games(&duplicated, IdPolicy::Any).unwrap()),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn bags() {
+        let games = Day2::parse(TEST_INPUT).unwrap();
+        let colors = &games.colors;
+        let game = |id: u32| &games.games[id as usize - 1];
+
+        let violations = game(3).violations(&Bag::default(), colors).unwrap();
+        assert_eq!(
+            violations,
+            [Violation {
+                game: 3,
+                set: 0,
+                color: "red".to_string(),
+                count: 20,
+                capacity: 12
+            }]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "Game 3, set 1: 20 red drawn, the bag holds 12"
+        );
+        assert_eq!(game(4).is_possible(&Bag::default(), colors), Ok(false));
+
+        let bag: Bag = "20 red, 13 green\n# enough blue for game 4\n15 blue"
+            .parse()
+            .unwrap();
+        assert_eq!(bag, Bag::new([("red", 20), ("green", 13), ("blue", 15)]));
+        assert_eq!(games.possible_ids_sum(&bag), Ok(15));
+        assert_eq!(bag.to_string().parse(), Ok(bag));
+
+        let bag: Bag = "3 green".parse().unwrap();
+        assert_eq!(game(1).violations(&bag, colors).unwrap().len(), 4);
+        assert_eq!(games.possible_ids_sum(&bag), Ok(0));
+
+        assert_eq!(
+            "1 red, 2 red".parse::<Bag>(),
+            Err("red is listed twice".to_string())
+        );
+        assert_eq!(
+            "1 dark red".parse::<Bag>(),
+            Err("Unexpected color `dark red`".to_string())
+        );
+        assert!("red".parse::<Bag>().is_err());
+    }
+
+    #[test]
+    fn extra_colors() {
+        let input = "Game 1: 3 blue, 4 red; 2 yellow, 2 green
+Game 2: 1 blue, 2 green, 1 red
+Game 3: 2 green, 5 yellow, 1 red; 2 blue";
+        let games = Day2::parse(input).unwrap();
+
+        let names: Vec<_> = games.colors.iter().map(|c| games.colors.name(c)).collect();
+        assert_eq!(names, ["blue", "red", "yellow", "green"]);
+        assert_eq!(
+            games.games[0].minimum_bag(&games.colors),
+            Bag::new([("blue", 3), ("red", 4), ("yellow", 2), ("green", 2)])
+        );
+
+        // Game 2 draws no yellow, its minimum bag holds none
+        assert_eq!(Day2::part2(&games), Ok(48 + 20));
+
+        let mut bag = Bag::default();
+        assert_eq!(Day2::part1(&games), Ok(2));
+        bag.unknown = UnknownColors::Ignore;
+        assert_eq!(games.possible_ids_sum(&bag), Ok(6));
+        bag.unknown = UnknownColors::Reject;
+        let e = games.possible_ids_sum(&bag).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Game 1, set 2: the bag holds no yellow cubes"
+        );
+
+        bag = "4 red, 2 green, 3 blue, 4 yellow".parse().unwrap();
+        assert_eq!(games.possible_ids_sum(&bag), Ok(3));
+
+        let e = Day2::parse("Game 1: 3 bl4e").unwrap_err();
+        assert_eq!(
+            (e.text.as_str(), e.message.as_str()),
+            ("bl4e", "Unexpected color")
+        );
+    }
+
+    #[test]
+    fn power_overflow() {
+        let game = "Game 1: 100000 red, 100000 blue, 100000 green";
+        assert_eq!(solve_part2(game), Ok(100000u64.pow(3)));
+
+        let game = "Game 1: 4000000000 red, 4000000000 blue, 4000000000 green";
+        let e = solve_part2(game).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 1, game));
+        assert_eq!(e.message, "Power of the minimum bag overflows");
+
+        let game = ": 4000000000 red, 4000000000 blue, 1 green";
+        let e = solve_part2(&format!("Game 1{game}\nGame 2{game}")).unwrap_err();
+        assert_eq!(
+            (e.line, e.message.as_str()),
+            (2, "Sum of the powers overflows")
+        );
+        assert_eq!(Bag::new([("red", 2), ("blue", 3)]).power(), Some(6));
+        assert_eq!(Bag::new([]).power(), Some(1));
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Day2::parse(TEST_INPUT).unwrap().stats();
+        assert_eq!(stats.colors, ["blue", "red", "green"]);
+
+        let game = &stats.games[2];
+        assert_eq!(
+            game.minimum,
+            Bag::new([("blue", 6), ("red", 20), ("green", 13)])
+        );
+        assert_eq!(
+            game.drawn,
+            Bag::new([("blue", 11), ("red", 25), ("green", 26)])
+        );
+        assert_eq!((game.constraining_set, game.power), (Some(0), Some(1560)));
+        assert_eq!(stats.games[0].constraining_set, Some(1));
+
+        let red = stats.minimum_distribution("red").unwrap();
+        assert_eq!((red.min, red.max, red.median), (1, 20, 6));
+        assert!((red.mean - 9.0).abs() < 1e-9);
+        assert_eq!(stats.power_distribution().unwrap().max, 1560);
+        assert_eq!(stats.minimum_distribution("yellow"), None);
+
+        let csv = stats.to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,sets,min_blue,min_red,min_green,drawn_blue,drawn_red,drawn_green,power,constraining_set"
+        );
+        assert_eq!(lines[3], "3,3,6,20,13,11,25,26,1560,1");
+        assert_eq!(lines.len(), 6);
+
+        let json = crate::json::parse(&stats.to_json().to_string()).unwrap();
+        let game = &json.get("games").and_then(Value::as_array).unwrap()[2];
+        let min_red = game.get("minimum").and_then(|m| m.get("red"));
+        assert_eq!(min_red.and_then(Value::as_f64), Some(20.0));
+        let power = json.get("distributions").and_then(|d| d.get("power"));
+        assert_eq!(
+            power.and_then(|p| p.get("mean")).and_then(Value::as_f64),
+            Some(457.2)
+        );
+
+        // Overflowing powers are left out rather than wrapped
+        let game = "Game 1: 4000000000 red, 4000000000 blue, 4000000000 green";
+        let stats = Day2::parse(game).unwrap().stats();
+        assert_eq!(stats.games[0].power, None);
+        assert_eq!(stats.power_distribution(), None);
+        assert!(stats.to_csv().ends_with(",4000000000,,1"));
+        assert!(stats.to_json().to_string().contains("\"power\":null"));
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT) {
+            assert_eq!(solve_part1(&input), Ok(8));
+            assert_eq!(solve_part2(&input), Ok(2286));
+        }
+    }
+}
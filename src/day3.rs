@@ -0,0 +1,982 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::{Grid, Pos};
+use crate::{input, ParseError, Solution};
+
+const DAY: u8 = 3;
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub enum ItemType {
+    Symbol(char),
+    Part(u32),
+}
+
+/// A symbol, or a part number spanning `len` cells rightwards from `start`.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Item {
+    pub start: Pos,
+    pub len: usize,
+    pub type_: ItemType,
+}
+
+impl Item {
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.y == self.start.y && (self.start.x..self.start.x + self.len).contains(&pos.x)
+    }
+
+    pub fn part_number(&self) -> Option<u32> {
+        match self.type_ {
+            ItemType::Part(number) => Some(number),
+            ItemType::Symbol(_) => None,
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.start.x..self.start.x + self.len).map(|x| Pos::new(x, self.start.y))
+    }
+
+    pub fn symbol(&self) -> Option<char> {
+        match self.type_ {
+            ItemType::Symbol(c) => Some(c),
+            ItemType::Part(_) => None,
+        }
+    }
+
+    fn new_part(pos: Pos, c: char) -> Self {
+        Self {
+            start: pos,
+            len: 1,
+            type_: ItemType::Part(c.to_digit(10).unwrap()),
+        }
+    }
+
+    fn new_symbol(pos: Pos, s: char) -> Self {
+        Self {
+            start: pos,
+            len: 1,
+            type_: ItemType::Symbol(s),
+        }
+    }
+}
+
+/// A set of symbols, e.g. `*` for gears.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbols {
+    Any,
+    Only(Vec<char>),
+}
+
+impl Symbols {
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            Symbols::Any => true,
+            Symbols::Only(symbols) => symbols.contains(&c),
+        }
+    }
+}
+
+/// Reads `any`, or the symbols of the set written together, e.g. `*#`.
+impl FromStr for Symbols {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        if s == "any" {
+            return Ok(Symbols::Any);
+        }
+
+        match s
+            .chars()
+            .find(|&c| c == '.' || c.is_ascii_digit() || c.is_whitespace())
+        {
+            Some(c) => Err(format!("`{c}` is not a symbol")),
+            None if s.is_empty() => Err("Expected symbols or `any`".to_string()),
+            None => Ok(Symbols::Only(s.chars().collect())),
+        }
+    }
+}
+
+impl fmt::Display for Symbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Symbols::Any => write!(f, "any"),
+            Symbols::Only(symbols) => write!(f, "{}", symbols.iter().collect::<String>()),
+        }
+    }
+}
+
+/// How many parts a symbol must touch. A count of 0 lets through symbols
+/// touching no part, which `FromStr` rejects: their ratio would be the empty
+/// product, 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn matches(self, parts: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => parts == n,
+            Arity::AtLeast(n) => parts >= n,
+        }
+    }
+}
+
+/// Reads `2` for exactly two parts, `2+` for at least two.
+impl FromStr for Arity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let error = |_| format!("Invalid arity `{s}`, expected `N` or `N+`");
+
+        let arity = match s.strip_suffix('+') {
+            Some(n) => n.parse().map(Arity::AtLeast).map_err(error)?,
+            None => s.parse().map(Arity::Exactly).map_err(error)?,
+        };
+        match arity {
+            Arity::Exactly(0) | Arity::AtLeast(0) => {
+                Err(format!("Invalid arity `{s}`, a gear needs at least 1 part"))
+            }
+            arity => Ok(arity),
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{n}"),
+            Arity::AtLeast(n) => write!(f, "{n}+"),
+        }
+    }
+}
+
+/// Which symbols are gears: by default `*` next to exactly two parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Symbols,
+    pub arity: Arity,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self {
+            symbols: Symbols::Only(vec!['*']),
+            arity: Arity::Exactly(2),
+        }
+    }
+}
+
+/// Items linked to each other through adjacent items, diagonals included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster<'a> {
+    /// In the order of `Schematic::items`.
+    pub items: Vec<&'a Item>,
+    /// Top left cell of the bounding box.
+    pub min: Pos,
+    /// Bottom right cell of the bounding box, included.
+    pub max: Pos,
+}
+
+impl<'a> Cluster<'a> {
+    pub fn parts(&self) -> impl Iterator<Item = &'a Item> + '_ {
+        self.items
+            .iter()
+            .copied()
+            .filter(|i| i.part_number().is_some())
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &'a Item> + '_ {
+        self.items.iter().copied().filter(|i| i.symbol().is_some())
+    }
+
+    pub fn part_sum(&self) -> u64 {
+        self.parts()
+            .filter_map(Item::part_number)
+            .map(u64::from)
+            .sum()
+    }
+}
+
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub items: Vec<Item>,
+    /// The index in `items` of the item covering each cell.
+    index: Grid<Option<usize>>,
+    /// 0-based index of the input line holding the first row, for error
+    /// reporting.
+    first_line: usize,
+}
+
+impl Schematic {
+    pub fn new(grid: Grid<char>, items: Vec<Item>) -> Self {
+        let mut index = Grid::new(grid.width(), grid.height(), None);
+        for (idx, item) in items.iter().enumerate() {
+            for pos in item.positions() {
+                index[pos] = Some(idx);
+            }
+        }
+
+        Self {
+            grid,
+            items,
+            index,
+            first_line: 0,
+        }
+    }
+
+    /// An error pointing at `item` in its row.
+    fn error(&self, item: &Item, message: &str) -> ParseError {
+        let row: String = self
+            .grid
+            .row(item.start.y)
+            .unwrap_or_default()
+            .iter()
+            .collect();
+        let offset = |x| row.char_indices().nth(x).map_or(row.len(), |(idx, _)| idx);
+        let token = &row[offset(item.start.x)..offset(item.start.x + item.len)];
+
+        ParseError::new(DAY, self.first_line + item.start.y, &row, token, message)
+    }
+
+    pub fn parts_candidates(&self) -> Vec<&Item> {
+        self.items
+            .iter()
+            .filter(|&i| matches!(i.type_, ItemType::Part(_)))
+            .collect()
+    }
+
+    pub fn item_at(&self, pos: Pos) -> Option<&Item> {
+        self.index.get(pos)?.map(|idx| &self.items[idx])
+    }
+
+    /// The items touching any cell of `item`, diagonals included, in the
+    /// order of `items`. None for an item outside the schematic.
+    pub fn neighbours_of(&self, item: &Item) -> Vec<&Item> {
+        self.neighbour_indices(item)
+            .into_iter()
+            .map(|idx| &self.items[idx])
+            .collect()
+    }
+
+    /// The indices in `items` of the neighbours of `item`.
+    fn neighbour_indices(&self, item: &Item) -> Vec<usize> {
+        let Some(&own) = self.index.get(item.start) else {
+            return vec![];
+        };
+
+        let mut neighbours: Vec<usize> = item
+            .positions()
+            .flat_map(|pos| self.grid.neighbours8(pos))
+            .filter_map(|pos| self.index[pos])
+            .filter(|&idx| Some(idx) != own)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+
+        neighbours
+    }
+
+    /// Whether `item` is a part number next to a symbol.
+    pub fn is_valid_part(&self, item: &Item) -> bool {
+        item.part_number().is_some() && !self.adjacent_symbols(item, &Symbols::Any).is_empty()
+    }
+
+    /// The symbols of `symbols` next to `item`.
+    pub fn adjacent_symbols(&self, item: &Item, symbols: &Symbols) -> Vec<&Item> {
+        self.neighbours_of(item)
+            .into_iter()
+            .filter(|n| n.symbol().is_some_and(|c| symbols.matches(c)))
+            .collect()
+    }
+
+    /// The parts next to `item`.
+    pub fn adjacent_parts(&self, item: &Item) -> Vec<&Item> {
+        self.neighbours_of(item)
+            .into_iter()
+            .filter(|n| n.part_number().is_some())
+            .collect()
+    }
+
+    /// The parts next to at least one symbol of `symbols`.
+    pub fn parts_next_to<'a>(&'a self, symbols: &'a Symbols) -> impl Iterator<Item = &'a Item> {
+        self.items.iter().filter(|item| {
+            item.part_number().is_some() && !self.adjacent_symbols(item, symbols).is_empty()
+        })
+    }
+
+    pub fn sum_parts_next_to(&self, symbols: &Symbols) -> u64 {
+        self.parts_next_to(symbols)
+            .filter_map(Item::part_number)
+            .map(u64::from)
+            .sum()
+    }
+
+    /// The symbols of `symbols` next to a number of parts matching `arity`,
+    /// with these parts.
+    pub fn symbols_with<'a>(
+        &'a self,
+        symbols: &'a Symbols,
+        arity: Arity,
+    ) -> impl Iterator<Item = (&'a Item, Vec<&'a Item>)> {
+        self.items
+            .iter()
+            .filter(|i| i.symbol().is_some_and(|c| symbols.matches(c)))
+            .map(|symbol| (symbol, self.adjacent_parts(symbol)))
+            .filter(move |(_, parts)| arity.matches(parts.len()))
+    }
+
+    /// The parts next to more than one symbol.
+    pub fn shared_parts(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().filter(|item| {
+            item.part_number().is_some() && self.adjacent_symbols(item, &Symbols::Any).len() > 1
+        })
+    }
+
+    /// The gears following `rule`, with their parts.
+    pub fn gears<'a>(
+        &'a self,
+        rule: &'a GearRule,
+    ) -> impl Iterator<Item = (&'a Item, Vec<&'a Item>)> {
+        self.symbols_with(&rule.symbols, rule.arity)
+    }
+
+    /// Sum of the products of the parts of each gear. Fails with the gear
+    /// making it overflow.
+    pub fn gear_ratio_sum<'a>(&'a self, rule: &'a GearRule) -> Result<u64, &'a Item> {
+        let mut sum = 0u64;
+        for (gear, parts) in self.gears(rule) {
+            sum = parts
+                .iter()
+                .filter_map(|p| p.part_number())
+                .try_fold(1u64, |ratio, n| ratio.checked_mul(n.into()))
+                .and_then(|ratio| sum.checked_add(ratio))
+                .ok_or(gear)?;
+        }
+
+        Ok(sum)
+    }
+
+    /// The connected groups of items, ordered by their first item. Numbers
+    /// next to nothing are clusters of their own.
+    pub fn clusters(&self) -> Vec<Cluster<'_>> {
+        let mut seen = vec![false; self.items.len()];
+        let mut clusters = vec![];
+
+        for first in 0..self.items.len() {
+            if seen[first] {
+                continue;
+            }
+
+            seen[first] = true;
+            let mut members = vec![first];
+            let mut queue = vec![first];
+            while let Some(idx) = queue.pop() {
+                for neighbour in self.neighbour_indices(&self.items[idx]) {
+                    if !seen[neighbour] {
+                        seen[neighbour] = true;
+                        members.push(neighbour);
+                        queue.push(neighbour);
+                    }
+                }
+            }
+            members.sort_unstable();
+
+            let items: Vec<&Item> = members.into_iter().map(|idx| &self.items[idx]).collect();
+            let min = Pos::new(
+                items.iter().map(|i| i.start.x).min().unwrap(),
+                items.iter().map(|i| i.start.y).min().unwrap(),
+            );
+            let max = Pos::new(
+                items.iter().map(|i| i.start.x + i.len - 1).max().unwrap(),
+                items.iter().map(|i| i.start.y).max().unwrap(),
+            );
+            clusters.push(Cluster { items, min, max });
+        }
+
+        clusters
+    }
+
+    /// What each cell shows, for rendering.
+    pub fn highlights(&self, rule: &GearRule) -> Grid<Highlight> {
+        let mut highlights = self
+            .index
+            .map(|idx| match idx.map(|idx| &self.items[idx].type_) {
+                None => Highlight::Empty,
+                Some(ItemType::Symbol(_)) => Highlight::Symbol,
+                Some(ItemType::Part(_)) => Highlight::Orphan,
+            });
+
+        for item in self.items.iter().filter(|i| self.is_valid_part(i)) {
+            for pos in item.positions() {
+                highlights[pos] = Highlight::Part;
+            }
+        }
+
+        for (gear, parts) in self.gears(rule) {
+            highlights[gear.start] = Highlight::Gear;
+            for pos in parts.iter().flat_map(|p| p.positions()) {
+                highlights[pos] = Highlight::GearPart;
+            }
+        }
+
+        highlights
+    }
+
+    /// The schematic with ANSI colours: valid parts in green, orphan numbers
+    /// in red, gears and their parts in bold magenta and yellow.
+    pub fn render_ansi(&self, rule: &GearRule) -> String {
+        let highlights = self.highlights(rule);
+        let mut out = String::new();
+
+        for (y, row) in self.grid.rows().enumerate() {
+            let mut current = Highlight::Empty;
+            for (x, &c) in row.iter().enumerate() {
+                let highlight = highlights[Pos::new(x, y)];
+                if highlight != current {
+                    out.push_str(highlight.ansi());
+                    current = highlight;
+                }
+                out.push(c);
+            }
+
+            if current != Highlight::Empty {
+                out.push_str(Highlight::Empty.ansi());
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// A binary PPM image with `scale` pixels square cells coloured by
+    /// highlight.
+    pub fn render_ppm(&self, rule: &GearRule, scale: usize) -> Vec<u8> {
+        let highlights = self.highlights(rule);
+        let (width, height) = (self.grid.width() * scale, self.grid.height() * scale);
+
+        let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+        for y in 0..height {
+            for x in 0..width {
+                out.extend(highlights[Pos::new(x / scale, y / scale)].rgb());
+            }
+        }
+
+        out
+    }
+
+    /// An SVG image of the schematic, coloured as with `render_ansi`.
+    pub fn render_svg(&self, rule: &GearRule) -> String {
+        const CELL_WIDTH: usize = 10;
+        const CELL_HEIGHT: usize = 16;
+
+        let highlights = self.highlights(rule);
+        let [r, g, b] = Highlight::Empty.rgb();
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             font-family=\"monospace\" font-size=\"14\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"rgb({r},{g},{b})\"/>\n",
+            self.grid.width() * CELL_WIDTH,
+            self.grid.height() * CELL_HEIGHT,
+        );
+
+        for (pos, &c) in self.grid.iter() {
+            let highlight = highlights[pos];
+            if highlight == Highlight::Empty {
+                continue;
+            }
+
+            let escaped = match c {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                c => c.to_string(),
+            };
+            let [r, g, b] = highlight.rgb();
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"rgb({r},{g},{b})\">{escaped}</text>\n",
+                pos.x * CELL_WIDTH,
+                (pos.y + 1) * CELL_HEIGHT - 4,
+            ));
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+/// How a cell of the schematic is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Empty,
+    Symbol,
+    /// A part number next to a symbol.
+    Part,
+    /// A number next to no symbol.
+    Orphan,
+    Gear,
+    /// A part next to a gear.
+    GearPart,
+}
+
+impl Highlight {
+    fn ansi(self) -> &'static str {
+        match self {
+            Highlight::Empty => "\x1b[0m",
+            Highlight::Symbol => "\x1b[0;1m",
+            Highlight::Part => "\x1b[0;32m",
+            Highlight::Orphan => "\x1b[0;31m",
+            Highlight::Gear => "\x1b[0;1;35m",
+            Highlight::GearPart => "\x1b[0;33m",
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Highlight::Empty => [24, 24, 24],
+            Highlight::Symbol => [230, 230, 230],
+            Highlight::Part => [60, 200, 80],
+            Highlight::Orphan => [220, 50, 50],
+            Highlight::Gear => [230, 60, 230],
+            Highlight::GearPart => [240, 200, 40],
+        }
+    }
+}
+
+/// Appends the items of row `y` of the grid. Fails with the column of the
+/// digit making a part number overflow.
+fn parse_row(row: &[char], y: usize, items: &mut Vec<Item>) -> Result<(), usize> {
+    let mut item: Option<&mut Item> = None;
+
+    for (x, &c) in row.iter().enumerate() {
+        let pos = Pos::new(x, y);
+        let is_digit = c.is_ascii_digit();
+
+        match item {
+            Some(Item {
+                type_: ItemType::Symbol(_),
+                ..
+            }) => {
+                if is_digit {
+                    // Symbol => Part
+                    items.push(Item::new_part(pos, c));
+                    item = items.last_mut();
+                } else if c != '.' {
+                    // Symbol => Symbol
+                    items.push(Item::new_symbol(pos, c));
+                    item = None;
+                } else {
+                    // Symbol => Nothing
+                    item = None;
+                }
+            }
+            Some(Item {
+                len,
+                type_: ItemType::Part(number),
+                ..
+            }) => {
+                if is_digit {
+                    // Part continues
+                    *number = number
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(c.to_digit(10).unwrap()))
+                        .ok_or(x)?;
+                    *len += 1;
+                } else if c != '.' {
+                    // Part => Symbol
+                    items.push(Item::new_symbol(pos, c));
+                    item = None;
+                } else {
+                    // Part => Nothing
+                    item = None;
+                }
+            }
+            None => {
+                if is_digit {
+                    // Nothing => Part
+                    items.push(Item::new_part(pos, c));
+                    item = items.last_mut();
+                } else if c != '.' {
+                    // Nothing => Symbol
+                    items.push(Item::new_symbol(pos, c));
+                    item = None;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The input line holding row `y`, for error reporting: rows are the lines
+/// of the input, spaces included.
+fn row_line(input: &str, y: usize) -> (usize, &str) {
+    input::raw_lines(input).nth(y).unwrap()
+}
+
+/// How rows shorter or longer than the first one are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    #[default]
+    Reject,
+    /// Rows are padded with `.` up to the longest one.
+    Pad,
+}
+
+fn parse_schematic(input: &str) -> Result<Schematic, ParseError> {
+    parse_schematic_with(input, RaggedRows::default())
+}
+
+pub fn parse_schematic_with(input: &str, ragged: RaggedRows) -> Result<Schematic, ParseError> {
+    // Padding is kept apart from the cells until the rows are checked
+    let cells = Grid::parse(input, None, |_, c| Ok::<_, ()>(Some(c))).unwrap();
+
+    if ragged == RaggedRows::Reject {
+        let widths: Vec<usize> = cells
+            .rows()
+            .map(|row| row.iter().flatten().count())
+            .collect();
+
+        if let Some(y) = widths.iter().position(|&width| width != widths[0]) {
+            let (columns, width) = (widths[0], widths[y]);
+            let (line_idx, line) = row_line(input, y);
+            let message = format!("Expected {columns} columns as on the first row, found {width}");
+            return Err(match line.char_indices().nth(columns) {
+                Some((extra, _)) => ParseError::new(DAY, line_idx, line, &line[extra..], message),
+                None => ParseError::missing(DAY, line_idx, line, message),
+            });
+        }
+    }
+
+    let grid = cells.map(|c| c.unwrap_or('.'));
+    let mut items = vec![];
+    for (y, row) in grid.rows().enumerate() {
+        parse_row(row, y, &mut items).map_err(|x| {
+            let (line_idx, line) = row_line(input, y);
+            let (byte_idx, c) = line.char_indices().nth(x).unwrap();
+            let digit = &line[byte_idx..byte_idx + c.len_utf8()];
+            ParseError::new(DAY, line_idx, line, digit, "Part number too large")
+        })?;
+    }
+
+    let first_line = input::raw_lines(input).next().map_or(0, |(idx, _)| idx);
+    Ok(Schematic {
+        first_line,
+        ..Schematic::new(grid, items)
+    })
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Schematic;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_schematic(input)
+    }
+
+    fn part1(schematic: &Self::Input) -> Result<u64, ParseError> {
+        Ok(schematic.sum_parts_next_to(&Symbols::Any))
+    }
+
+    fn part2(schematic: &Self::Input) -> Result<u64, ParseError> {
+        schematic
+            .gear_ratio_sum(&GearRule::default())
+            .map_err(|gear| schematic.error(gear, "Gear ratio sum overflows"))
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    Day3::part1(&Day3::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    Day3::part2(&Day3::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn day3() {
+        assert_eq!(solve_part1(TEST_INPUT), Ok(4361));
+
+        assert_eq!(solve_part2(TEST_INPUT), Ok(467835));
+
+        if let Some(input) = crate::input::reference(3) {
+            assert_eq!(solve_part1(&input), Ok(543867));
+            assert_eq!(solve_part2(&input), Ok(79613331));
+        }
+    }
+
+    #[test]
+    fn items() {
+        let schematic = Day3::parse(TEST_INPUT).unwrap();
+        assert_eq!((schematic.grid.width(), schematic.grid.height()), (10, 10));
+        assert_eq!(schematic.grid[Pos::new(3, 1)], '*');
+
+        let part = &schematic.items[1];
+        assert_eq!(part.type_, ItemType::Part(114));
+        assert_eq!(
+            part.positions().collect::<Vec<_>>(),
+            [Pos::new(5, 0), Pos::new(6, 0), Pos::new(7, 0)]
+        );
+        assert!(part.contains(Pos::new(7, 0)) && !part.contains(Pos::new(8, 0)));
+
+        let e = solve_part1("\n..............\n  *99999999999").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 13, "9"));
+        assert_eq!(e.message, "Part number too large");
+    }
+
+    #[test]
+    fn neighbours() {
+        let schematic = Day3::parse(TEST_INPUT).unwrap();
+        let types = |item: &Item| -> Vec<ItemType> {
+            schematic
+                .neighbours_of(item)
+                .into_iter()
+                .map(|n| n.type_.clone())
+                .collect()
+        };
+
+        let gear = schematic.item_at(Pos::new(3, 1)).unwrap();
+        assert_eq!(types(gear), [ItemType::Part(467), ItemType::Part(35)]);
+
+        let part = schematic.item_at(Pos::new(1, 0)).unwrap();
+        assert_eq!(part.type_, ItemType::Part(467));
+        assert_eq!(types(part), [ItemType::Symbol('*')]);
+        assert_eq!(types(schematic.item_at(Pos::new(5, 0)).unwrap()), []);
+        assert_eq!(schematic.item_at(Pos::new(4, 0)), None);
+        assert_eq!(schematic.item_at(Pos::new(10, 0)), None);
+
+        let outside = Item::new_symbol(Pos::new(10, 3), '*');
+        assert_eq!(types(&outside), []);
+    }
+
+    #[test]
+    fn wide_schematic() {
+        // Every `12*34` block is a gear, the blank row keeps the rows apart
+        let row = "12*34.".repeat(2000);
+        let blank = ".".repeat(row.len());
+        let input = [row.as_str(), &blank, &row].join("\n");
+
+        assert_eq!(solve_part1(&input), Ok(2 * 2000 * 46));
+        assert_eq!(solve_part2(&input), Ok(2 * 2000 * 12 * 34));
+    }
+
+    #[test]
+    fn clusters() {
+        let schematic = Day3::parse(TEST_INPUT).unwrap();
+        let clusters = schematic.clusters();
+
+        let sums: Vec<u64> = clusters.iter().map(Cluster::part_sum).collect();
+        assert_eq!(sums, [467 + 35, 114, 633, 617, 592, 58, 755 + 598, 664]);
+
+        let first = &clusters[0];
+        assert_eq!((first.min, first.max), (Pos::new(0, 0), Pos::new(3, 2)));
+        assert_eq!(
+            first.symbols().map(|s| s.start).collect::<Vec<_>>(),
+            [Pos::new(3, 1)]
+        );
+        assert_eq!(
+            (clusters[6].min, clusters[6].max),
+            (Pos::new(5, 7), Pos::new(8, 9))
+        );
+        assert_eq!(clusters[5].items.len(), 1);
+
+        // Clusters with a symbol hold exactly the valid parts
+        let valid: u64 = clusters
+            .iter()
+            .filter(|c| c.symbols().next().is_some())
+            .map(Cluster::part_sum)
+            .sum();
+        assert_eq!(valid, 4361);
+        assert_eq!(
+            clusters.iter().map(|c| c.items.len()).sum::<usize>(),
+            schematic.items.len()
+        );
+
+        // Parts chain symbols together
+        let chain = Day3::parse("1*2..\n...#3").unwrap();
+        let clusters = chain.clusters();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(
+            (clusters[0].min, clusters[0].max),
+            (Pos::new(0, 0), Pos::new(4, 1))
+        );
+        assert_eq!(clusters[0].part_sum(), 6);
+    }
+
+    #[test]
+    fn render() {
+        let schematic = Day3::parse("467..114..\n...*......\n..35..633&").unwrap();
+
+        let rule = GearRule::default();
+        let highlights = schematic.highlights(&rule);
+        let row = |y| -> Vec<Highlight> { highlights.row(y).unwrap()[..4].to_vec() };
+        use Highlight::*;
+        assert_eq!(row(0), [GearPart, GearPart, GearPart, Empty]);
+        assert_eq!(row(1), [Empty, Empty, Empty, Gear]);
+        assert_eq!(highlights[Pos::new(5, 0)], Orphan);
+        assert_eq!(highlights[Pos::new(8, 2)], Part);
+        assert_eq!(highlights[Pos::new(9, 2)], Symbol);
+
+        let ansi = schematic.render_ansi(&rule);
+        assert!(ansi.starts_with("\x1b[0;33m467\x1b[0m..\x1b[0;31m114\x1b[0m..\n"));
+        assert_eq!(ansi.lines().count(), 3);
+
+        let ppm = schematic.render_ppm(&rule, 2);
+        let header = b"P6\n20 6\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 20 * 6 * 3);
+        assert_eq!(
+            ppm[header.len()..header.len() + 3],
+            Highlight::GearPart.rgb()
+        );
+
+        let svg = schematic.render_svg(&rule);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">&amp;</text>"));
+        assert_eq!(svg.matches("<text").count(), 13);
+    }
+
+    #[test]
+    fn queries() {
+        let schematic = Day3::parse(TEST_INPUT).unwrap();
+        let numbers = |items: Vec<&Item>| -> Vec<u32> {
+            items.into_iter().filter_map(Item::part_number).collect()
+        };
+
+        assert_eq!(
+            schematic.sum_parts_next_to(&"*".parse().unwrap()),
+            467 + 35 + 617 + 755 + 598
+        );
+        assert_eq!(
+            schematic.sum_parts_next_to(&"#$".parse().unwrap()),
+            633 + 664
+        );
+        assert_eq!(schematic.sum_parts_next_to(&Symbols::Any), 4361);
+        assert_eq!(schematic.shared_parts().count(), 0);
+
+        let lonely: Vec<_> = schematic
+            .symbols_with(&Symbols::Any, Arity::Exactly(1))
+            .map(|(symbol, parts)| (symbol.symbol().unwrap(), numbers(parts)))
+            .collect();
+        assert_eq!(
+            lonely,
+            [
+                ('#', vec![633]),
+                ('*', vec![617]),
+                ('+', vec![592]),
+                ('$', vec![664])
+            ]
+        );
+        assert_eq!(
+            schematic
+                .symbols_with(&Symbols::Any, Arity::AtLeast(1))
+                .count(),
+            6
+        );
+
+        let rule = GearRule {
+            symbols: Symbols::Any,
+            arity: Arity::AtLeast(1),
+        };
+        assert_eq!(
+            schematic.gear_ratio_sum(&rule),
+            Ok(467 * 35 + 633 + 617 + 592 + 664 + 755 * 598)
+        );
+
+        let shared = Day3::parse("1.#\n.23\n*..").unwrap();
+        assert_eq!(numbers(shared.shared_parts().collect()), [23]);
+
+        assert_eq!("2+".parse(), Ok(Arity::AtLeast(2)));
+        assert_eq!("3".parse(), Ok(Arity::Exactly(3)));
+        assert!("x".parse::<Arity>().is_err());
+        assert!("0".parse::<Arity>().is_err() && "0+".parse::<Arity>().is_err());
+        assert_eq!("any".parse(), Ok(Symbols::Any));
+        assert!("*.".parse::<Symbols>().is_err());
+        assert_eq!(Arity::AtLeast(2).to_string(), "2+");
+        assert_eq!(Symbols::Only(vec!['*', '#']).to_string(), "*#");
+    }
+
+    #[test]
+    fn ratio_overflow() {
+        let rule = GearRule {
+            symbols: Symbols::Any,
+            arity: Arity::AtLeast(4),
+        };
+        let schematic = Day3::parse("999*999\n999.999").unwrap();
+        assert_eq!(schematic.gear_ratio_sum(&rule), Ok(999u64.pow(4)));
+        assert_eq!(solve_part2("99999*99999"), Ok(99999 * 99999));
+
+        // Two parts can't overflow a u64, but three can, and so can the sum
+        let input = "\n4000000000*4000000000\n..........4000000000.";
+        let schematic = Day3::parse(input).unwrap();
+        let gear = schematic.item_at(Pos::new(10, 0)).unwrap();
+        let rule = GearRule {
+            arity: Arity::Exactly(3),
+            ..GearRule::default()
+        };
+        assert_eq!(schematic.gear_ratio_sum(&rule), Err(gear));
+
+        let row = "4000000000*4000000000.4000000000*4000000000";
+        let e = solve_part2(row).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 33, "*"));
+        assert_eq!(e.message, "Gear ratio sum overflows");
+        let e = solve_part2(&format!("\n\n{row}")).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 33));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = |input| Day3::parse(input).err().unwrap();
+
+        let e = error("467..\n...*.\n..35..6\n1");
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 6, ".6"));
+        assert_eq!(e.message, "Expected 5 columns as on the first row, found 7");
+
+        let e = error("467..\n...*");
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 5, ""));
+        assert_eq!(error("467..\n\n...*.").line, 2);
+
+        // Blank lines around the schematic aren't rows
+        assert_eq!(solve_part1("\n467..\n...*.\n\n"), Ok(467));
+
+        let schematic = parse_schematic_with("467\n...*\n..35", RaggedRows::Pad).unwrap();
+        assert_eq!((schematic.grid.width(), schematic.grid.height()), (4, 3));
+        assert_eq!(schematic.grid.row(0).unwrap(), ['4', '6', '7', '.']);
+        assert_eq!(Day3::part2(&schematic), Ok(467 * 35));
+        assert_eq!(Day3::part1(&schematic), Ok(467 + 35));
+
+        // Columns are counted on the rows as read, leading spaces included
+        let schematic = Day3::parse("  1*\n....").unwrap();
+        assert_eq!((schematic.grid.width(), schematic.grid.height()), (4, 2));
+        let e = error("  1*\n...");
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(e.message, "Expected 4 columns as on the first row, found 3");
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT) {
+            assert_eq!(solve_part1(&input), Ok(4361));
+            assert_eq!(solve_part2(&input), Ok(467835));
+        }
+    }
+}
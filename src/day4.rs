@@ -0,0 +1,238 @@
+use std::collections::HashSet;
+
+use crate::ids::{IdPolicy, Ids};
+use crate::input;
+use crate::{ParseError, Solution};
+
+const DAY: u8 = 4;
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: u32,
+    pub own: HashSet<i32>,
+    pub winning: HashSet<i32>,
+}
+
+impl Card {
+    pub fn matching(&self) -> usize {
+        self.winning.intersection(&self.own).count()
+    }
+}
+
+/// The copies of one card: its original plus the ones won from earlier cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCopies {
+    pub total: u32,
+    /// Cards that won copies of this one, by index in the table, with how
+    /// many they won.
+    pub won_from: Vec<(usize, u32)>,
+}
+
+/// The pile of scratchcards once every won copy has been scratched.
+#[derive(Debug)]
+pub struct Scratchcards {
+    cards: Vec<CardCopies>,
+}
+
+impl Scratchcards {
+    /// Scratches the cards in ID order: a card with N matches wins copies of
+    /// the cards with the next N IDs, whichever are in the table.
+    pub fn new(cards: &[Card]) -> Self {
+        let mut copies: Vec<_> = cards
+            .iter()
+            .map(|_| CardCopies {
+                total: 1,
+                won_from: vec![],
+            })
+            .collect();
+
+        let mut order: Vec<_> = (0..cards.len()).collect();
+        order.sort_by_key(|&idx| cards[idx].id);
+
+        for (position, &idx) in order.iter().enumerate() {
+            let won = copies[idx].total;
+
+            // Wins past the end of the table are lost
+            let last_id = cards[idx].id.saturating_add(cards[idx].matching() as u32);
+            for &next in order[position + 1..]
+                .iter()
+                .skip_while(|&&next| cards[next].id == cards[idx].id)
+                .take_while(|&&next| cards[next].id <= last_id)
+            {
+                copies[next].total += won;
+                copies[next].won_from.push((idx, won));
+            }
+        }
+
+        Self { cards: copies }
+    }
+
+    /// Copies of the card at `idx`, its original included.
+    pub fn copies(&self, idx: usize) -> Option<&CardCopies> {
+        self.cards.get(idx)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CardCopies> {
+        self.cards.iter()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.cards.iter().map(|c| c.total).sum()
+    }
+}
+
+fn load_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_cards(input, IdPolicy::default())
+}
+
+/// Parses the cards, accepting the IDs allowed by `ids`.
+pub fn parse_cards(input: &str, ids: IdPolicy) -> Result<Vec<Card>, ParseError> {
+    let mut ids = Ids::new(DAY, "Card", ids);
+
+    input::lines(input)
+        .map(|(line_idx, l)| {
+            let error =
+                |token: &str, message: &str| ParseError::new(DAY, line_idx, l, token, message);
+
+            let split_values = |s: &str| -> Result<HashSet<i32>, ParseError> {
+                s.split(' ')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse().map_err(|_| error(s, "Invalid number")))
+                    .collect()
+            };
+
+            let (id, numbers) = ids.header(line_idx, l)?;
+            let Some((own, winning)) = numbers.split_once(" | ") else {
+                return Err(error(numbers, "Expected ` | ` between number lists"));
+            };
+
+            Ok(Card {
+                id,
+                own: split_values(own)?,
+                winning: split_values(winning)?,
+            })
+        })
+        .collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Card>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_cards(input)
+    }
+
+    fn part1(cards: &Self::Input) -> Result<u32, ParseError> {
+        Ok(cards
+            .iter()
+            .map(|c| {
+                let matching = c.matching();
+                if matching > 0 {
+                    2u32.pow((matching - 1) as _)
+                } else {
+                    0
+                }
+            })
+            .sum())
+    }
+
+    fn part2(cards: &Self::Input) -> Result<u32, ParseError> {
+        Ok(Scratchcards::new(cards).total())
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    Day4::part1(&Day4::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u32, ParseError> {
+    Day4::part2(&Day4::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn day4() {
+        assert_eq!(solve_part1(TEST_INPUT), Ok(13));
+
+        assert_eq!(solve_part2(TEST_INPUT), Ok(30));
+
+        if let Some(input) = crate::input::reference(4) {
+            assert_eq!(solve_part1(&input), Ok(28750));
+            assert_eq!(solve_part2(&input), Ok(10212704));
+        }
+    }
+
+    #[test]
+    fn copies() {
+        let cards = Day4::parse(TEST_INPUT).unwrap();
+        let scratchcards = Scratchcards::new(&cards);
+
+        let totals: Vec<_> = scratchcards.iter().map(|c| c.total).collect();
+        assert_eq!(totals, [1, 2, 4, 8, 14, 1]);
+        assert_eq!(
+            scratchcards.copies(4).unwrap().won_from,
+            [(0, 1), (2, 4), (3, 8)]
+        );
+        assert_eq!(scratchcards.copies(0).unwrap().won_from, []);
+        assert_eq!(scratchcards.copies(6), None);
+    }
+
+    #[test]
+    fn end_of_table() {
+        // The last card gets its copies, wins past it are dropped
+        let input = "Card 1: 1 2 | 1 3
+Card 2: 4 5 | 4 5
+Card 3: 6 7 | 6 8";
+        let scratchcards = Scratchcards::new(&Day4::parse(input).unwrap());
+
+        let totals: Vec<_> = scratchcards.iter().map(|c| c.total).collect();
+        assert_eq!(totals, [1, 2, 3]);
+        assert_eq!(scratchcards.copies(2).unwrap().won_from, [(1, 2)]);
+        assert_eq!(solve_part2(input), Ok(6));
+    }
+
+    #[test]
+    fn card_ids() {
+        let e = Day4::parse("Card 2: 1 | 1").unwrap_err();
+        assert_eq!(
+            (e.text.as_str(), e.message.as_str()),
+            ("2", "Expected Card 1")
+        );
+
+        // Copies go to the following IDs, whatever the order of the table
+        let lines: Vec<_> = TEST_INPUT.lines().collect();
+        let reordered = [lines[3], lines[0], lines[5], lines[2], lines[1], lines[4]].join("\n");
+        assert!(Day4::parse(&reordered).is_err());
+        let cards = parse_cards(&reordered, IdPolicy::Unique).unwrap();
+        let totals: Vec<_> = Scratchcards::new(&cards).iter().map(|c| c.total).collect();
+        assert_eq!(totals, [8, 1, 1, 4, 2, 14]);
+
+        // Card 2 is missing: card 1 wins copies of cards 3 to 5 only
+        let subset = [lines[0], lines[2], lines[3], lines[4]].join("\n");
+        let cards = parse_cards(&subset, IdPolicy::Unique).unwrap();
+        let totals: Vec<_> = Scratchcards::new(&cards).iter().map(|c| c.total).collect();
+        assert_eq!(totals, [1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT) {
+            assert_eq!(solve_part1(&input), Ok(13));
+            assert_eq!(solve_part2(&input), Ok(30));
+        }
+    }
+}
@@ -0,0 +1,1246 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::{input, ParseError, Solution};
+
+const DAY: u8 = 5;
+
+#[derive(Debug)]
+pub struct CategoryMapEntry {
+    pub destination: usize,
+    pub source: usize,
+    pub range: usize,
+}
+
+/// Why an entry can't be added to a `CategoryMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryError {
+    Empty,
+    DestinationOverflow,
+    SourceOverflow,
+    /// The source range overlaps the one of the entry at `index`.
+    Overlap {
+        source: Range<usize>,
+        other: Range<usize>,
+        index: usize,
+    },
+}
+
+impl fmt::Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EntryError::Empty => write!(f, "Empty range"),
+            EntryError::DestinationOverflow => write!(f, "Destination range overflows"),
+            EntryError::SourceOverflow => write!(f, "Source range overflows"),
+            EntryError::Overlap { source, other, .. } => write!(
+                f,
+                "Source range {}..{} overlaps {}..{}",
+                source.start, source.end, other.start, other.end
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EntryError {}
+
+/// A map between two categories, its entries non-empty and with disjoint
+/// sources.
+#[derive(Debug)]
+pub struct CategoryMap {
+    pub source: String,
+    pub destination: String,
+    entries: Vec<CategoryMapEntry>,
+}
+
+impl CategoryMap {
+    pub fn new(source: &str, destination: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            entries: vec![],
+        }
+    }
+
+    pub fn entries(&self) -> &[CategoryMapEntry] {
+        &self.entries
+    }
+
+    pub fn add_entry(&mut self, entry: CategoryMapEntry) -> Result<(), EntryError> {
+        if entry.range == 0 {
+            return Err(EntryError::Empty);
+        }
+        if entry.destination.checked_add(entry.range).is_none() {
+            return Err(EntryError::DestinationOverflow);
+        }
+        let Some(end) = entry.source.checked_add(entry.range) else {
+            return Err(EntryError::SourceOverflow);
+        };
+
+        let overlapping = self
+            .entries
+            .iter()
+            .position(|e| e.source < end && entry.source < e.source + e.range);
+        if let Some(index) = overlapping {
+            let other = &self.entries[index];
+            return Err(EntryError::Overlap {
+                source: entry.source..end,
+                other: other.source..other.source + other.range,
+                index,
+            });
+        }
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn map(&self, v: usize) -> usize {
+        for e in &self.entries {
+            let range = e.source..e.source + e.range;
+            if range.contains(&v) {
+                return e.destination + v - e.source;
+            }
+        }
+
+        v
+    }
+
+    /// All the values mapped to `v`: the ones shifted there by an entry, and
+    /// `v` itself when no entry covers it.
+    pub fn unmap(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let shifted = self
+            .entries
+            .iter()
+            .filter(move |e| (e.destination..e.destination + e.range).contains(&v))
+            .map(move |e| e.source + v - e.destination);
+        let unchanged = self
+            .entries
+            .iter()
+            .all(|e| !(e.source..e.source + e.range).contains(&v))
+            .then_some(v);
+
+        shifted.chain(unchanged)
+    }
+
+    /// Maps a whole interval at once: the parts covered by an entry are
+    /// shifted to its destination, the others pass through unchanged.
+    pub fn map_range(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.source < range.end && range.start < e.source + e.range)
+            .collect();
+        entries.sort_by_key(|e| e.source);
+
+        let mut mapped = vec![];
+        let mut cursor = range.start;
+
+        for e in entries {
+            let start = cursor.max(e.source);
+            let end = range.end.min(e.source + e.range);
+            if start >= end {
+                continue;
+            }
+
+            if cursor < start {
+                mapped.push(cursor..start);
+            }
+
+            mapped.push(e.destination + start - e.source..e.destination + end - e.source);
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            mapped.push(cursor..range.end);
+        }
+
+        mapped
+    }
+
+    /// A single map applying `self` then `next`.
+    pub fn compose(&self, next: &CategoryMap) -> PiecewiseMap {
+        PiecewiseMap::from(self).then(&next.into())
+    }
+}
+
+/// A run of values shifted together by a `PiecewiseMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Range<usize>,
+    pub destination: usize,
+}
+
+/// A category map kept as sorted and disjoint pieces, so that lookups are a
+/// binary search. Values outside every piece pass through unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pub source: String,
+    pub destination: String,
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    pub fn identity(category: &str) -> Self {
+        Self {
+            source: category.to_string(),
+            destination: category.to_string(),
+            pieces: vec![],
+        }
+    }
+
+    /// Builds the map from sorted and disjoint pieces, dropping the ones that
+    /// leave values unchanged and merging the ones that continue each other.
+    fn from_pieces(source: &str, destination: &str, pieces: impl Iterator<Item = Piece>) -> Self {
+        let mut merged: Vec<Piece> = vec![];
+
+        for piece in pieces.filter(|p| !p.source.is_empty() && p.source.start != p.destination) {
+            match merged.last_mut() {
+                Some(last)
+                    if last.source.end == piece.source.start
+                        && last.destination + last.source.len() == piece.destination =>
+                {
+                    last.source.end = piece.source.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+
+        Self {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            pieces: merged,
+        }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    pub fn map(&self, v: usize) -> usize {
+        let idx = self.pieces.partition_point(|p| p.source.end <= v);
+        match self.pieces.get(idx) {
+            Some(p) if p.source.contains(&v) => p.destination + v - p.source.start,
+            _ => v,
+        }
+    }
+
+    /// Splits `range` into the pieces it goes through, including the ones
+    /// passing through unchanged.
+    fn split(&self, range: Range<usize>) -> Vec<Piece> {
+        let mut split = vec![];
+        let mut cursor = range.start;
+
+        let first = self.pieces.partition_point(|p| p.source.end <= range.start);
+        for p in self.pieces[first..]
+            .iter()
+            .take_while(|p| p.source.start < range.end)
+        {
+            if cursor < p.source.start {
+                split.push(Piece {
+                    source: cursor..p.source.start,
+                    destination: cursor,
+                });
+            }
+
+            let start = cursor.max(p.source.start);
+            let end = range.end.min(p.source.end);
+            split.push(Piece {
+                source: start..end,
+                destination: p.destination + start - p.source.start,
+            });
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            split.push(Piece {
+                source: cursor..range.end,
+                destination: cursor,
+            });
+        }
+
+        split
+    }
+
+    /// A single map applying `self` then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let pieces = self.split(0..usize::MAX).into_iter().flat_map(|p| {
+            let shift = p.source.start;
+            next.split(p.destination..p.destination + p.source.len())
+                .into_iter()
+                .map(move |n| {
+                    let start = shift + n.source.start - p.destination;
+                    Piece {
+                        source: start..start + n.source.len(),
+                        destination: n.destination,
+                    }
+                })
+        });
+
+        Self::from_pieces(&self.source, &next.destination, pieces)
+    }
+}
+
+impl From<&CategoryMap> for PiecewiseMap {
+    fn from(map: &CategoryMap) -> Self {
+        let mut entries: Vec<_> = map.entries.iter().collect();
+        entries.sort_by_key(|e| e.source);
+
+        // Entries are disjoint, sorting them is enough
+        let pieces = entries.into_iter().map(|e| Piece {
+            source: e.source..e.source + e.range,
+            destination: e.destination,
+        });
+
+        Self::from_pieces(&map.source, &map.destination, pieces)
+    }
+}
+
+/// Prints the map in the almanac format, so it can be read back.
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-to-{} map:", self.source, self.destination)?;
+        for p in &self.pieces {
+            write!(
+                f,
+                "\n{} {} {}",
+                p.destination,
+                p.source.start,
+                p.source.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of seeds, kept as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedRanges {
+    ranges: Vec<Range<usize>>,
+}
+
+impl SeedRanges {
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Number of seeds in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|r| r.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, seed: usize) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= seed);
+        self.ranges.get(idx).is_some_and(|r| r.contains(&seed))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(|r| r.clone())
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(ra), Some(rb)) = (a.peek(), b.peek()) {
+            let start = ra.start.max(rb.start);
+            let end = ra.end.min(rb.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Drop whichever range ends first, it can't overlap anything else
+            if ra.end < rb.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+
+        for r in &self.ranges {
+            let mut start = r.start;
+
+            while let Some(o) = others.peek() {
+                if o.end <= start {
+                    others.next();
+                    continue;
+                }
+                if o.start >= r.end {
+                    break;
+                }
+
+                if start < o.start {
+                    ranges.push(start..o.start);
+                }
+                start = o.end;
+
+                if o.end > r.end {
+                    break;
+                }
+                others.next();
+            }
+
+            if start < r.end {
+                ranges.push(start..r.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl FromIterator<Range<usize>> for SeedRanges {
+    fn from_iter<I: IntoIterator<Item = Range<usize>>>(iter: I) -> Self {
+        let mut sorted: Vec<_> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut ranges: Vec<Range<usize>> = vec![];
+        for r in sorted {
+            match ranges.last_mut() {
+                // Overlapping or adjacent: merge
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => ranges.push(r),
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacError {
+    UnknownCategory(String),
+    /// Maps only go forward along the chain.
+    Backward {
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlmanacError::UnknownCategory(category) => write!(f, "Unknown category `{category}`"),
+            AlmanacError::Backward { from, to } => {
+                write!(f, "`{to}` comes before `{from}` in the almanac")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+/// The seeds and the maps chained from `seed` to the last category,
+/// whatever their order in the input.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    /// The seeds read as `start length` pairs, or why they can't be.
+    pub seed_ranges: Result<SeedRanges, ParseError>,
+    /// Maps in chain order: each one starts where the previous one ends.
+    pub stage_maps: Vec<CategoryMap>,
+}
+
+impl Almanac {
+    pub const FIRST_CATEGORY: &'static str = "seed";
+
+    /// Category names along the chain, starting with `seed`.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(Self::FIRST_CATEGORY)
+            .chain(self.stage_maps.iter().map(|m| m.destination.as_str()))
+    }
+
+    fn category_idx(&self, category: &str) -> Result<usize, AlmanacError> {
+        self.categories()
+            .position(|c| c == category)
+            .ok_or_else(|| AlmanacError::UnknownCategory(category.to_string()))
+    }
+
+    /// The maps to apply in turn to go from category `from` to `to`.
+    pub fn path(&self, from: &str, to: &str) -> Result<&[CategoryMap], AlmanacError> {
+        let start = self.category_idx(from)?;
+        let end = self.category_idx(to)?;
+        if end < start {
+            return Err(AlmanacError::Backward {
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+
+        Ok(&self.stage_maps[start..end])
+    }
+
+    /// Converts `value` from category `from` to `to`, e.g. the fertilizer
+    /// needed by seed 79.
+    pub fn convert(&self, from: &str, value: usize, to: &str) -> Result<usize, AlmanacError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(value, |value, stage| stage.map(value)))
+    }
+
+    /// A single map converting category `from` to `to`.
+    pub fn compose(&self, from: &str, to: &str) -> Result<PiecewiseMap, AlmanacError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(PiecewiseMap::identity(from), |map, stage| {
+                map.then(&stage.into())
+            }))
+    }
+
+    /// The map from seeds to the last category.
+    pub fn pipeline(&self) -> PiecewiseMap {
+        let last = self.categories().last().unwrap();
+        self.compose(Self::FIRST_CATEGORY, last).unwrap()
+    }
+
+    /// All the values of category `from` converted to `value` in `to`, e.g.
+    /// the seeds ending up at location 82, sorted.
+    pub fn unconvert(
+        &self,
+        to: &str,
+        value: usize,
+        from: &str,
+    ) -> Result<Vec<usize>, AlmanacError> {
+        let mut values = vec![value];
+        for stage in self.path(from, to)?.iter().rev() {
+            values = values.into_iter().flat_map(|v| stage.unmap(v)).collect();
+        }
+
+        values.sort_unstable();
+        values.dedup();
+
+        Ok(values)
+    }
+
+    /// Searches locations upwards until one maps back to a seed in `seeds`.
+    /// Much slower than mapping the seed intervals forward.
+    pub fn lowest_reachable_location(&self, seeds: &SeedRanges) -> Option<usize> {
+        fn reaches(stages: &[CategoryMap], value: usize, seeds: &SeedRanges) -> bool {
+            match stages.split_last() {
+                Some((stage, previous)) => stage.unmap(value).any(|v| reaches(previous, v, seeds)),
+                None => seeds.contains(value),
+            }
+        }
+
+        if seeds.is_empty() {
+            return None;
+        }
+
+        (0..=usize::MAX).find(|&location| reaches(&self.stage_maps, location, seeds))
+    }
+}
+
+fn parse_numbers(line_idx: usize, line: &str, s: &str) -> Result<Vec<usize>, ParseError> {
+    s.split(' ')
+        .map(|n| {
+            n.parse()
+                .map_err(|_| ParseError::new(DAY, line_idx, line, n, "Invalid number"))
+        })
+        .collect()
+}
+
+/// Reads the `seeds` values of `line`, written as `s`, as `start length`
+/// pairs. Only part 2 needs them.
+fn parse_seed_ranges(
+    line_idx: usize,
+    line: &str,
+    s: &str,
+    seeds: &[usize],
+) -> Result<SeedRanges, ParseError> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(ParseError::missing(
+            DAY,
+            line_idx,
+            line,
+            "Expected `<start> <length>` seed pairs",
+        ));
+    }
+
+    let lengths: Vec<&str> = s.split(' ').skip(1).step_by(2).collect();
+    let ranges: SeedRanges = seeds
+        .chunks_exact(2)
+        .zip(&lengths)
+        .map(|(pair, len)| match pair[0].checked_add(pair[1]) {
+            Some(end) => Ok(pair[0]..end),
+            None => Err(ParseError::new(
+                DAY,
+                line_idx,
+                line,
+                len,
+                "Seed range overflows",
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+
+    if ranges.is_empty() {
+        return Err(ParseError::new(
+            DAY,
+            line_idx,
+            line,
+            lengths[0],
+            "Every seed range is empty",
+        ));
+    }
+
+    Ok(ranges)
+}
+
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let mut input_line_reader = input::lines(input);
+
+    // Parse seeds
+    let Some((line_idx, line)) = input_line_reader.next() else {
+        return Err(ParseError::missing(DAY, 0, "", "Expected `seeds: `"));
+    };
+    let Some(seeds) = line.strip_prefix("seeds: ") else {
+        return Err(ParseError::new(
+            DAY,
+            line_idx,
+            line,
+            line,
+            "Expected `seeds: `",
+        ));
+    };
+    let (values, seeds) = (seeds, parse_numbers(line_idx, line, seeds)?);
+    let seed_ranges = parse_seed_ranges(line_idx, line, values, &seeds);
+
+    if let Some((line_idx, line)) = input_line_reader.next() {
+        if !line.is_empty() {
+            return Err(ParseError::new(
+                DAY,
+                line_idx,
+                line,
+                line,
+                "Expected an empty line",
+            ));
+        }
+    }
+
+    // Parse stages, along with their title line
+    let mut stage_maps: Vec<(CategoryMap, usize, &str)> = vec![];
+    'outer: while let Some((title_idx, title)) = input_line_reader.next() {
+        let Some((source, destination)) = title
+            .strip_suffix(" map:")
+            .and_then(|names| names.split_once("-to-"))
+        else {
+            return Err(ParseError::new(
+                DAY,
+                title_idx,
+                title,
+                title,
+                "Expected `<source>-to-<destination> map:`",
+            ));
+        };
+
+        if let Some((_, previous_idx, _)) = stage_maps.iter().find(|(m, ..)| m.source == source) {
+            return Err(ParseError::new(
+                DAY,
+                title_idx,
+                title,
+                source,
+                format!("`{source}` is already mapped on line {}", previous_idx + 1),
+            ));
+        }
+
+        stage_maps.push((CategoryMap::new(source, destination), title_idx, title));
+        let current_stage = &mut stage_maps.last_mut().unwrap().0;
+        let name = format!("{source}-to-{destination}");
+        let mut entry_lines = vec![];
+
+        loop {
+            let (line_idx, line) = match input_line_reader.next() {
+                Some((line_idx, line)) => {
+                    if line.is_empty() {
+                        break;
+                    }
+
+                    (line_idx, line)
+                }
+                None => break 'outer,
+            };
+
+            let values = parse_numbers(line_idx, line, line)?;
+            let [destination, source, range] = values[..] else {
+                return Err(ParseError::new(
+                    DAY,
+                    line_idx,
+                    line,
+                    line,
+                    "Expected `<destination> <source> <range>`",
+                ));
+            };
+
+            let entry = CategoryMapEntry {
+                destination,
+                source,
+                range,
+            };
+            if let Err(e) = current_stage.add_entry(entry) {
+                let tokens: Vec<_> = line.split(' ').collect();
+                let token = match e {
+                    EntryError::Empty => tokens[2],
+                    EntryError::DestinationOverflow => tokens[0],
+                    EntryError::SourceOverflow | EntryError::Overlap { .. } => {
+                        &line[tokens[0].len() + 1..]
+                    }
+                };
+                let message = match e {
+                    EntryError::Overlap { index, .. } => {
+                        format!("{e} from line {}", entry_lines[index] + 1)
+                    }
+                    e => e.to_string(),
+                };
+
+                return Err(ParseError::new(
+                    DAY,
+                    line_idx,
+                    line,
+                    token,
+                    format!("{message} in `{name}` map"),
+                ));
+            }
+            entry_lines.push(line_idx);
+        }
+    }
+
+    // Chain the maps, starting from seeds
+    let mut chain = vec![];
+    let mut category = Almanac::FIRST_CATEGORY.to_string();
+    while let Some(idx) = stage_maps.iter().position(|(m, ..)| m.source == category) {
+        let (stage, title_idx, title) = stage_maps.remove(idx);
+
+        let loops = stage.destination == Almanac::FIRST_CATEGORY
+            || stage.destination == stage.source
+            || chain
+                .iter()
+                .any(|m: &CategoryMap| m.source == stage.destination);
+        if loops {
+            let destination = &title[title.len() - " map:".len() - stage.destination.len()..];
+            return Err(ParseError::new(
+                DAY,
+                title_idx,
+                title,
+                &destination[..stage.destination.len()],
+                format!("Map loops back to `{}`", stage.destination),
+            ));
+        }
+
+        category = stage.destination.clone();
+        chain.push(stage);
+    }
+
+    if let Some((stage, title_idx, title)) = stage_maps.first() {
+        return Err(ParseError::new(
+            DAY,
+            *title_idx,
+            title,
+            &title[..stage.source.len()],
+            format!("`{}` is not reachable from `seed`", stage.source),
+        ));
+    }
+
+    Ok(Almanac {
+        seeds,
+        seed_ranges,
+        stage_maps: chain,
+    })
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Almanac;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_almanac(input)
+    }
+
+    fn part1(almanac: &Self::Input) -> Result<usize, ParseError> {
+        let pipeline = almanac.pipeline();
+
+        Ok(almanac
+            .seeds
+            .iter()
+            .map(|&seed| pipeline.map(seed))
+            .min()
+            .unwrap())
+    }
+
+    fn part2(almanac: &Self::Input) -> Result<usize, ParseError> {
+        let mut seeds = almanac.seed_ranges.clone()?;
+
+        for stage in &almanac.stage_maps {
+            seeds = seeds
+                .ranges()
+                .iter()
+                .flat_map(|range| stage.map_range(range.clone()))
+                .collect();
+        }
+
+        Ok(seeds.min().unwrap())
+    }
+}
+
+/// Maps every seed one by one, only kept to cross-check `Day5::part2`.
+fn part2_brute_force(almanac: &Almanac) -> Result<usize, ParseError> {
+    Ok(almanac
+        .seed_ranges
+        .as_ref()
+        .map_err(Clone::clone)?
+        .iter()
+        .map(|seed| {
+            let mut value = seed;
+
+            for stage in &almanac.stage_maps {
+                value = stage.map(value);
+            }
+
+            value
+        })
+        .min()
+        .unwrap())
+}
+
+/// Reverse search from the locations, only kept to cross-check `Day5::part2`.
+fn part2_reverse_search(almanac: &Almanac) -> Result<usize, ParseError> {
+    let seeds = almanac.seed_ranges.as_ref().map_err(Clone::clone)?;
+
+    Ok(almanac.lowest_reachable_location(seeds).unwrap())
+}
+
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    Day5::part1(&Day5::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    Day5::part2(&Day5::parse(input)?)
+}
+
+pub fn solve_part2_brute_force(input: &str) -> Result<usize, ParseError> {
+    part2_brute_force(&Day5::parse(input)?)
+}
+
+pub fn solve_part2_reverse_search(input: &str) -> Result<usize, ParseError> {
+    part2_reverse_search(&Day5::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn day5() {
+        assert_eq!(solve_part1(TEST_INPUT), Ok(35));
+
+        assert_eq!(solve_part2(TEST_INPUT), Ok(46));
+
+        if let Some(input) = crate::input::reference(5) {
+            assert_eq!(solve_part1(&input), Ok(322500873));
+            assert_eq!(solve_part2(&input), Ok(108956227));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn map_range() {
+        let mut map = CategoryMap::new("a", "b");
+        map.add_entry(CategoryMapEntry {
+            destination: 100,
+            source: 10,
+            range: 5,
+        })
+        .unwrap();
+        map.add_entry(CategoryMapEntry {
+            destination: 0,
+            source: 20,
+            range: 5,
+        })
+        .unwrap();
+
+        assert_eq!(map.map_range(0..5), [0..5]);
+        assert_eq!(map.map_range(10..15), [100..105]);
+        assert_eq!(
+            map.map_range(5..30),
+            [5..10, 100..105, 15..20, 0..5, 25..30]
+        );
+        assert_eq!(map.map_range(12..22), [102..105, 15..20, 0..2]);
+
+        // Entries are checked as they are added, so that `map` and the
+        // composed maps can't disagree on overlaps
+        let entry = |destination, source, range| CategoryMapEntry {
+            destination,
+            source,
+            range,
+        };
+        assert_eq!(
+            map.add_entry(entry(50, 14, 10)),
+            Err(EntryError::Overlap {
+                source: 14..24,
+                other: 10..15,
+                index: 0
+            })
+        );
+        assert_eq!(map.add_entry(entry(50, 0, 0)), Err(EntryError::Empty));
+        assert_eq!(
+            map.add_entry(entry(usize::MAX, 0, 1)),
+            Err(EntryError::DestinationOverflow)
+        );
+        assert_eq!(
+            map.add_entry(entry(0, usize::MAX, 1)),
+            Err(EntryError::SourceOverflow)
+        );
+        assert_eq!(map.entries().len(), 2);
+        assert_eq!(map.add_entry(entry(50, 15, 5)), Ok(()));
+        assert_eq!(map.map(17), PiecewiseMap::from(&map).map(17));
+    }
+
+    fn seed_ranges(ranges: &[Range<usize>]) -> SeedRanges {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn normalised_seed_ranges() {
+        // Nested, adjacent, duplicate and empty ranges
+        let seeds = seed_ranges(&[20..30, 0..10, 22..25, 10..12, 40..45, 40..45, 50..50]);
+        assert_eq!(seeds.ranges(), [0..12, 20..30, 40..45]);
+        assert_eq!(seeds.len(), 27);
+        assert_eq!(seeds.min(), Some(0));
+        assert!(seeds.contains(11) && !seeds.contains(12) && seeds.contains(44));
+
+        // The day5 input is made of (start, length) pairs
+        let almanac = Day5::parse("seeds: 79 14 55 13 60 5").unwrap();
+        let seeds = almanac.seed_ranges.unwrap();
+        assert_eq!(seeds.ranges(), [55..68, 79..93]);
+        assert_eq!(seeds.iter().count(), 27);
+    }
+
+    #[test]
+    fn invalid_seed_ranges() {
+        // Only part 2 reads pairs
+        let maps = TEST_INPUT.split_once("\n\n").unwrap().1;
+        let input = format!("seeds: 79 14 55\n\n{maps}");
+        assert_eq!(solve_part1(&input), Ok(43));
+        let e = solve_part2(&input).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 16));
+        assert_eq!(e.message, "Expected `<start> <length>` seed pairs");
+
+        let e = solve_part2("seeds: 5 1 18446744073709551615 2").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 33, "2"));
+        assert_eq!(e.message, "Seed range overflows");
+        assert_eq!(
+            solve_part2_reverse_search("seeds: 1 2 3"),
+            solve_part2("seeds: 1 2 3")
+        );
+    }
+
+    #[test]
+    fn seed_range_operations() {
+        let a = seed_ranges(&[0..10, 20..30]);
+        let b = seed_ranges(&[5..25, 28..29, 40..50]);
+
+        assert_eq!(a.union(&b).ranges(), [0..30, 40..50]);
+        assert_eq!(a.intersection(&b).ranges(), [5..10, 20..25, 28..29]);
+        assert_eq!(a.difference(&b).ranges(), [0..5, 25..28, 29..30]);
+        assert_eq!(b.difference(&a).ranges(), [10..20, 40..50]);
+
+        assert_eq!(a.intersection(&a), a);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.union(&SeedRanges::default()), a);
+        assert!(a.intersection(&seed_ranges(&[10..20, 30..40])).is_empty());
+    }
+
+    #[test]
+    fn named_categories() {
+        let almanac = Day5::parse(TEST_INPUT).unwrap();
+        let categories: Vec<_> = almanac.categories().collect();
+        assert_eq!(
+            categories,
+            [
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ]
+        );
+
+        assert_eq!(almanac.convert("seed", 79, "fertilizer"), Ok(81));
+        assert_eq!(almanac.convert("seed", 79, "location"), Ok(82));
+        assert_eq!(almanac.convert("humidity", 78, "location"), Ok(82));
+        assert_eq!(almanac.convert("soil", 14, "soil"), Ok(14));
+        assert_eq!(
+            almanac.convert("location", 1, "seed"),
+            Err(AlmanacError::Backward {
+                from: "location".to_string(),
+                to: "seed".to_string()
+            })
+        );
+        assert_eq!(
+            almanac.convert("seed", 1, "dirt"),
+            Err(AlmanacError::UnknownCategory("dirt".to_string()))
+        );
+    }
+
+    #[test]
+    fn reordered_sections() {
+        let (seeds, maps) = TEST_INPUT.split_once("\n\n").unwrap();
+        let mut sections: Vec<_> = maps.split("\n\n").collect();
+        sections.reverse();
+        let input = format!("{seeds}\n\n{}", sections.join("\n\n"));
+
+        assert_eq!(solve_part1(&input), Ok(35));
+        assert_eq!(solve_part2(&input), Ok(46));
+    }
+
+    #[test]
+    fn broken_chain() {
+        let e = solve_part1("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nwater-to-light map:\n1 2 3")
+            .unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (6, "water"));
+
+        let e = solve_part1("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nseed-to-water map:\n1 2 3")
+            .unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (6, "seed"));
+
+        let e = solve_part1("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n1 2 3")
+            .unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (6, "seed"));
+        assert_eq!(e.message, "Map loops back to `seed`");
+
+        let e = solve_part1("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-soil map:\n1 2 3")
+            .unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (6, 9, "soil"));
+        assert_eq!(e.message, "Map loops back to `soil`");
+    }
+
+    #[test]
+    fn reverse_search() {
+        let almanac = Day5::parse(TEST_INPUT).unwrap();
+
+        assert_eq!(almanac.unconvert("location", 82, "seed"), Ok(vec![79]));
+        assert_eq!(almanac.unconvert("soil", 50, "seed"), Ok(vec![98]));
+        for location in 0..110 {
+            for seed in almanac.unconvert("location", location, "seed").unwrap() {
+                assert_eq!(almanac.convert("seed", seed, "location"), Ok(location));
+            }
+        }
+
+        let mut map = CategoryMap::new("a", "b");
+        map.add_entry(CategoryMapEntry {
+            destination: 10,
+            source: 20,
+            range: 5,
+        })
+        .unwrap();
+        assert_eq!(map.unmap(12).collect::<Vec<_>>(), [22, 12]);
+        assert_eq!(map.unmap(22).count(), 0);
+        assert_eq!(map.unmap(30).collect::<Vec<_>>(), [30]);
+
+        let seeds: SeedRanges = almanac.seeds.iter().map(|&s| s..s + 1).collect();
+        assert_eq!(almanac.lowest_reachable_location(&seeds), Some(35));
+        assert_eq!(
+            almanac.lowest_reachable_location(&SeedRanges::default()),
+            None
+        );
+        assert_eq!(solve_part2_reverse_search(TEST_INPUT), Ok(46));
+    }
+
+    #[test]
+    fn composed_pipeline() {
+        let almanac = Day5::parse(TEST_INPUT).unwrap();
+        let pipeline = almanac.pipeline();
+
+        let locations: Vec<_> = almanac.seeds.iter().map(|&s| pipeline.map(s)).collect();
+        assert_eq!(locations, [82, 43, 86, 35]);
+        assert!(pipeline.pieces().is_sorted_by_key(|p| p.source.start));
+
+        let fertilizer = almanac.compose("seed", "fertilizer").unwrap();
+        assert_eq!(fertilizer.map(79), 81);
+        assert_eq!(
+            almanac.compose("water", "water"),
+            Ok(PiecewiseMap::identity("water"))
+        );
+
+        // The printed table reads back as an almanac
+        let input = format!("seeds: 79 14 55 13\n\n{pipeline}");
+        assert!(input.contains("\nseed-to-location map:\n"));
+        assert_eq!(solve_part1(&input), Ok(35));
+    }
+
+    #[test]
+    fn composed_maps() {
+        // Maps over 0..1000 with disjoint sources, as in the puzzle
+        let mut rng = crate::testing::rng();
+        let mut next = |bound: usize| rng(bound as u64) as usize;
+        let mut random_map = |name: &str| {
+            let mut cuts: Vec<_> = (0..next(8)).map(|_| next(1000)).collect();
+            cuts.sort_unstable();
+
+            let mut map = CategoryMap::new(name, name);
+            cuts.dedup();
+            for bounds in cuts.windows(2) {
+                if next(3) > 0 {
+                    map.add_entry(CategoryMapEntry {
+                        destination: next(1000),
+                        source: bounds[0],
+                        range: bounds[1] - bounds[0],
+                    })
+                    .unwrap();
+                }
+            }
+
+            map
+        };
+
+        for _ in 0..200 {
+            let maps: Vec<_> = (0..4).map(|_| random_map("a")).collect();
+            let composed = maps.iter().fold(PiecewiseMap::identity("a"), |m, stage| {
+                m.then(&stage.into())
+            });
+            assert!(composed
+                .pieces()
+                .windows(2)
+                .all(|w| w[0].source.end <= w[1].source.start));
+            assert_eq!(
+                maps[0].compose(&maps[1]),
+                PiecewiseMap::from(&maps[0]).then(&(&maps[1]).into())
+            );
+
+            for v in 0..2100 {
+                let sequential = maps.iter().fold(v, |v, stage| stage.map(v));
+                assert_eq!(composed.map(v), sequential, "{v} through {maps:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn range_boundaries() {
+        let almanac = Day5::parse(TEST_INPUT).unwrap();
+
+        // `50 98 2` maps seeds 98 and 99 only, `52 50 48` seeds 50 to 97
+        let soil: Vec<_> = [49, 50, 97, 98, 99, 100]
+            .into_iter()
+            .map(|seed| almanac.convert("seed", seed, "soil").unwrap())
+            .collect();
+        assert_eq!(soil, [49, 52, 99, 50, 51, 100]);
+
+        for stage in &almanac.stage_maps {
+            for v in 0..120 {
+                assert_eq!(stage.map_range(v..v + 1)[0].start, stage.map(v));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_entries() {
+        let error = |entries: &str| {
+            let input = format!("seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n{entries}");
+            let e = solve_part1(&input).unwrap_err();
+            (e.line, e.text, e.message)
+        };
+
+        assert_eq!(
+            error("52 99 48"),
+            (
+                5,
+                "99 48".to_string(),
+                "Source range 99..147 overlaps 98..100 from line 4 in `seed-to-soil` map"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            error("10 20 0"),
+            (
+                5,
+                "0".to_string(),
+                "Empty range in `seed-to-soil` map".to_string()
+            )
+        );
+        assert_eq!(
+            error(&format!("{} 0 1", usize::MAX)).2,
+            "Destination range overflows in `seed-to-soil` map"
+        );
+        assert_eq!(
+            error(&format!("0 {} 2", usize::MAX - 1)).2,
+            "Source range overflows in `seed-to-soil` map"
+        );
+
+        // Adjacent ranges don't overlap
+        assert!(solve_part1("seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n52 50 48").is_ok());
+    }
+
+    #[test]
+    fn brute_force() {
+        assert_eq!(solve_part2_brute_force(TEST_INPUT), Ok(46));
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT) {
+            assert_eq!(solve_part1(&input), Ok(35));
+            assert_eq!(solve_part2(&input), Ok(46));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::num::{IntErrorKind, ParseIntError};
+use std::ops::RangeInclusive;
+
+use crate::{input, ParseError, Solution};
+
+const DAY: u8 = 6;
+
+#[derive(Debug)]
+pub struct Race {
+    pub time: u128,
+    pub distance: u128,
+}
+
+impl Race {
+    /// Whether holding the button for `hold` beats the record. A distance
+    /// overflowing 128 bits beats any record.
+    fn beats(&self, hold: u128) -> bool {
+        hold.checked_mul(self.time - hold)
+            .is_none_or(|distance| distance > self.distance)
+    }
+
+    /// First winning hold time, from the roots of d^2 - time * d + distance,
+    /// (time -/+ sqrt(disc)) / 2. Needs time^2 to fit in 128 bits.
+    fn first_winning_closed_form(&self) -> u128 {
+        let disc = self.time * self.time - 4 * self.distance;
+
+        // Start from the rounded root and step to the first winning hold
+        // time: the integer square root is off by at most one.
+        let mut first = (self.time - disc.isqrt()) / 2;
+        while first > 0 && self.beats(first - 1) {
+            first -= 1;
+        }
+        while !self.beats(first) {
+            first += 1;
+        }
+
+        first
+    }
+
+    /// First winning hold time, by bisection over the increasing half of the
+    /// parabola. Works for any time, at the cost of ~128 steps.
+    fn first_winning_bisection(&self) -> u128 {
+        let (mut lo, mut hi) = (0, self.time / 2);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.beats(mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        hi
+    }
+
+    /// Hold times beating the record, i.e. the integers `d` with
+    /// `d * (time - d) > distance`. Empty when the record can't be beaten.
+    pub fn winning_range(&self) -> RangeInclusive<u128> {
+        // The best hold time is time / 2
+        if !self.beats(self.time / 2) {
+            return RangeInclusive::new(1, 0);
+        }
+
+        let first = if self.time <= u64::MAX as u128 {
+            self.first_winning_closed_form()
+        } else {
+            self.first_winning_bisection()
+        };
+
+        // The parabola is symmetric around time / 2
+        first..=self.time - first
+    }
+
+    pub fn run(&self) -> u128 {
+        let range = self.winning_range();
+        if range.is_empty() {
+            0
+        } else {
+            range.end() - range.start() + 1
+        }
+    }
+
+    pub fn run_brute_force(&self) -> u128 {
+        let mut winning = 0;
+
+        for d in 1..self.time {
+            if self.beats(d) {
+                winning += 1;
+            }
+        }
+
+        winning
+    }
+}
+
+fn parse_number(
+    line_idx: usize,
+    line: &str,
+    token: &str,
+    digits: &str,
+) -> Result<u128, ParseError> {
+    digits.parse().map_err(|e: ParseIntError| {
+        let message = match e.kind() {
+            IntErrorKind::PosOverflow => "Number doesn't fit in 128 bits",
+            _ => "Invalid number",
+        };
+
+        ParseError::new(DAY, line_idx, line, token, message)
+    })
+}
+
+/// Splits the input into its `Time:` and `Distance:` lines, returning the
+/// index, full text and values of each.
+fn split_lines(input: &str) -> Result<[(usize, &str, &str); 2], ParseError> {
+    const LABELS: [&str; 2] = ["Time:", "Distance:"];
+
+    let lines: Vec<_> = input::lines(input).collect();
+    if let Some(&(line_idx, line)) = lines.get(LABELS.len()) {
+        return Err(ParseError::new(
+            DAY,
+            line_idx,
+            line,
+            line,
+            "Unexpected line",
+        ));
+    }
+
+    let mut values = [(0, "", ""); 2];
+    for (idx, label) in LABELS.iter().enumerate() {
+        let Some(&(line_idx, line)) = lines.get(idx) else {
+            let (line_idx, line) = idx.checked_sub(1).map_or((0, ""), |idx| lines[idx]);
+            return Err(ParseError::missing(
+                DAY,
+                line_idx,
+                line,
+                format!("Expected a `{label}` line"),
+            ));
+        };
+
+        let Some(l) = line.strip_prefix(label) else {
+            return Err(ParseError::new(
+                DAY,
+                line_idx,
+                line,
+                line,
+                format!("Expected `{label}`"),
+            ));
+        };
+
+        values[idx] = (line_idx, line, l.trim());
+    }
+
+    Ok(values)
+}
+
+fn parse_input1(input: &str) -> Result<Vec<Race>, ParseError> {
+    let lines = split_lines(input)?;
+    let values = lines
+        .iter()
+        .map(|&(line_idx, line, l)| {
+            l.split(' ')
+                .filter(|s| !s.is_empty())
+                .map(|s| parse_number(line_idx, line, s, s))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if values[0].len() != values[1].len() {
+        let (line_idx, line, l) = lines[1];
+        return Err(ParseError::new(
+            DAY,
+            line_idx,
+            line,
+            l,
+            format!("Expected {} distances", values[0].len()),
+        ));
+    }
+
+    // Each race wins at most `time` ways, so the part 1 product can't
+    // overflow if the product of the times doesn't.
+    let (line_idx, line, l) = lines[0];
+    values[0]
+        .iter()
+        .try_fold(1u128, |acc, &time| acc.checked_mul(time))
+        .ok_or_else(|| {
+            ParseError::new(
+                DAY,
+                line_idx,
+                line,
+                l,
+                "Product of times doesn't fit in 128 bits",
+            )
+        })?;
+
+    Ok(values[0]
+        .iter()
+        .zip(&values[1])
+        .map(|(&time, &distance)| Race { time, distance })
+        .collect())
+}
+
+fn parse_input2(input: &str) -> Result<Race, ParseError> {
+    let lines = split_lines(input)?;
+    let [time, distance] = lines.map(|(line_idx, line, l)| {
+        let digits: String = l.chars().filter(|&c| c != ' ').collect();
+        parse_number(line_idx, line, l, &digits)
+    });
+
+    Ok(Race {
+        time: time?,
+        distance: distance?,
+    })
+}
+
+/// Both readings of the sheet: one race per column for part 1, and a single
+/// race with the spaces between digits ignored for part 2. The latter may
+/// not fit in 128 bits when the former do, which only fails part 2.
+#[derive(Debug)]
+pub struct Sheet {
+    pub races: Vec<Race>,
+    pub race: Result<Race, ParseError>,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Sheet;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Sheet {
+            races: parse_input1(input)?,
+            race: parse_input2(input),
+        })
+    }
+
+    fn part1(sheet: &Self::Input) -> Result<u128, ParseError> {
+        Ok(sheet.races.iter().map(|r| r.run()).product())
+    }
+
+    fn part2(sheet: &Self::Input) -> Result<u128, ParseError> {
+        Ok(sheet.race.as_ref().map_err(Clone::clone)?.run())
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u128, ParseError> {
+    Day6::part1(&Day6::parse(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u128, ParseError> {
+    Day6::part2(&Day6::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::rng;
+
+    const TEST_INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn day6() {
+        assert_eq!(solve_part1(TEST_INPUT), Ok(288));
+
+        assert_eq!(solve_part2(TEST_INPUT), Ok(71503));
+
+        if let Some(input) = crate::input::reference(6) {
+            assert_eq!(solve_part1(&input), Ok(131376));
+            assert_eq!(solve_part2(&input), Ok(34123437));
+        }
+    }
+
+    #[test]
+    fn line_endings() {
+        for input in crate::input::encodings(TEST_INPUT) {
+            assert_eq!(solve_part1(&input), Ok(288));
+            assert_eq!(solve_part2(&input), Ok(71503));
+        }
+    }
+
+    fn brute_force_range(race: &Race) -> Option<(u128, u128)> {
+        let winning: Vec<_> = (1..race.time)
+            .filter(|&d| d * (race.time - d) > race.distance)
+            .collect();
+
+        Some((*winning.first()?, *winning.last()?))
+    }
+
+    #[test]
+    fn closed_form() {
+        // Both roots are integers: 10 and 20 only tie the record
+        let race = Race {
+            time: 30,
+            distance: 200,
+        };
+        assert_eq!(race.winning_range(), 11..=19);
+
+        for (time, distance) in [(0, 0), (1, 0), (2, 0), (2, 1), (4, 4), (4, 3)] {
+            let race = Race { time, distance };
+            assert_eq!(race.run(), race.run_brute_force(), "{race:?}");
+        }
+
+        // Cross-check random races against the brute force
+        let mut next = rng();
+        for _ in 0..2000 {
+            let time = next(300) as u128;
+            let distance = next((time * time / 4 + 10) as u64) as u128;
+            let race = Race { time, distance };
+
+            assert_eq!(race.run(), race.run_brute_force(), "{race:?}");
+
+            let range = race.winning_range();
+            let expected = brute_force_range(&race);
+            if range.is_empty() {
+                assert_eq!(expected, None, "{race:?}");
+            } else {
+                assert_eq!(Some((*range.start(), *range.end())), expected, "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn large_races() {
+        // Both searches agree wherever the closed form applies
+        let mut next = rng();
+        for _ in 0..2000 {
+            let time = (next(u64::MAX) as u128) << next(32) >> 32;
+            let max_distance = time / 2 * (time - time / 2);
+            let distance = max_distance - (next(u64::MAX) as u128 % (max_distance + 1));
+            let race = Race { time, distance };
+
+            if race.beats(time / 2) {
+                assert_eq!(
+                    race.first_winning_closed_form(),
+                    race.first_winning_bisection(),
+                    "{race:?}"
+                );
+            }
+        }
+
+        // Beyond 64 bits only bisection applies, check the range boundaries
+        let race = Race {
+            time: 10u128.pow(30) + 7,
+            distance: 10u128.pow(36),
+        };
+        let range = race.winning_range();
+        assert!(race.beats(*range.start()) && !race.beats(range.start() - 1));
+        assert!(race.beats(*range.end()) && !race.beats(range.end() + 1));
+
+        // 31-digit times once the spaces are removed
+        let input = "Time: 10000000000 0000000000 0000000007
+Distance: 1000000000000 000000000000 000000000000";
+        assert_eq!(solve_part2(input), Ok(race.run()));
+
+        let input = "Time: 1000000000000000000000000000000000000000
+Distance: 1";
+        let e = solve_part2(input).unwrap_err();
+        assert_eq!(
+            (e.line, e.message.as_str()),
+            (1, "Number doesn't fit in 128 bits")
+        );
+
+        // Both races fit on their own, not once joined
+        let input = "Time: 10000000000000000000 10000000000000000000
+Distance: 1 1";
+        let race = Race {
+            time: 10u128.pow(19),
+            distance: 1,
+        };
+        assert_eq!(solve_part1(input), Ok(race.run() * race.run()));
+        let e = solve_part2(input).unwrap_err();
+        assert_eq!(
+            (e.line, e.message.as_str()),
+            (1, "Number doesn't fit in 128 bits")
+        );
+
+        let input = "Time: 10000000000000 10000000000000 10000000000000
+Distance: 1 1 1";
+        let e = solve_part1(input).unwrap_err();
+        assert_eq!(e.message, "Product of times doesn't fit in 128 bits");
+    }
+}
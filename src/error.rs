@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// A malformed puzzle input, located down to the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// The offending text, empty when something is missing.
+    pub text: String,
+    /// The whole line the error was found on.
+    pub line_text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Builds an error on line `line_idx` (0-based) pointing at `token`,
+    /// which must be a subslice of `line`. Pass an empty slice at the end of
+    /// `line` to report something missing.
+    pub fn new(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset + token.len() <= line.len())
+            .unwrap_or(0);
+
+        Self {
+            day,
+            line: line_idx + 1,
+            column: line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            line_text: line.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds an error pointing past the end of `line`.
+    pub fn missing(day: u8, line_idx: usize, line: &str, message: impl Into<String>) -> Self {
+        Self::new(day, line_idx, line, &line[line.len()..], message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line_number = self.line.to_string();
+        let margin = " ".repeat(line_number.len());
+
+        writeln!(
+            f,
+            "Day {} input, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        writeln!(f, "{margin} |")?;
+        writeln!(f, "{line_number} | {}", self.line_text)?;
+        write!(
+            f,
+            "{margin} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.text.chars().count().max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret() {
+        let line = "Game 3: 8 purple";
+        let e = ParseError::new(2, 2, line, &line[10..], "Unexpected color");
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 11, "purple"));
+        assert_eq!(
+            e.to_string(),
+            "Day 2 input, line 3, column 11: Unexpected color
+  |
+3 | Game 3: 8 purple
+  |           ^^^^^^"
+        );
+
+        let e = ParseError::missing(6, 0, "Time:", "Expected a number");
+        assert_eq!((e.column, e.text.as_str()), (6, ""));
+        assert!(e.to_string().ends_with("|      ^"));
+    }
+}
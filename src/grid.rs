@@ -0,0 +1,260 @@
+use std::ops::{Index, IndexMut};
+
+use crate::input;
+
+/// A cell of a `Grid`: `x` is the column and `y` the row, both from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// The position moved by `(dx, dy)`, if it doesn't go below 0.
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+/// Offsets to the left, right, top and bottom neighbours.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// `NEIGHBOURS4` and the diagonals.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid as wide as the longest row, padding the others with
+    /// `fill`.
+    pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character of each line of `input`, empty lines
+    /// around it ignored. Spaces are cells, short lines are padded with
+    /// `fill`.
+    pub fn parse<E>(
+        input: &str,
+        fill: T,
+        mut cell: impl FnMut(Pos, char) -> Result<T, E>,
+    ) -> Result<Self, E>
+    where
+        T: Clone,
+    {
+        let rows = input::raw_lines(input)
+            .enumerate()
+            .map(|(y, (_, line))| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| cell(Pos::new(x, y), c))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_rows(rows, fill))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.y * self.width + pos.x])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y).unwrap())
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// The positions at the given offsets from `pos` that are in the grid.
+    pub fn neighbours<'a>(
+        &self,
+        pos: Pos,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets
+            .iter()
+            .filter_map(move |&offset| pos.offset(offset))
+            .filter(move |p| p.x < width && p.y < height)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, &NEIGHBOURS8)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// One cell per character, short lines padded with `fill`.
+    pub fn from_text(input: &str, fill: char) -> Self {
+        Self::parse(input, fill, |_, c| Ok::<_, ()>(c)).unwrap()
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of a {width}x{height} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let mut grid = Grid::from_text("ab\nc\ndef\n", '.');
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Pos::new(2, 2)], 'f');
+        assert_eq!(grid.get(Pos::new(2, 1)), Some(&'.'));
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+
+        grid[Pos::new(1, 1)] = 'x';
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["ab.", "cx.", "def"]);
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.column(1).unwrap().collect::<String>(), "bxe");
+        assert!(grid.column(3).is_none());
+
+        let (pos, c) = grid.iter().nth(4).unwrap();
+        assert_eq!((pos, *c), (Pos::new(1, 1), 'x'));
+        assert_eq!(
+            grid.map(|c| c.is_alphabetic())
+                .iter()
+                .filter(|(_, &b)| b)
+                .count(),
+            7
+        );
+
+        let digits: Result<Grid<u32>, Pos> =
+            Grid::parse("12\n3x", 0, |pos, c| c.to_digit(10).ok_or(pos));
+        assert_eq!(digits, Err(Pos::new(1, 1)));
+        assert_eq!(Grid::<char>::from_text("\n\n", '.').positions().count(), 0);
+
+        // Leading spaces are cells, not indentation
+        let grid = Grid::from_text("\r\n  #\r\n###\r\n", '.');
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["  #", "###"]);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 2, 0);
+
+        let corner: Vec<_> = grid.neighbours4(Pos::new(0, 0)).collect();
+        assert_eq!(corner, [Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(grid.neighbours8(Pos::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Pos::new(2, 1)).count(), 3);
+        assert_eq!(grid.neighbours4(Pos::new(1, 0)).count(), 3);
+
+        assert_eq!(Pos::new(0, 5).offset((-1, 0)), None);
+        assert_eq!(Pos::new(2, 5).offset((-1, 1)), Some(Pos::new(1, 6)));
+    }
+}
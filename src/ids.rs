@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use crate::ParseError;
+
+/// Which `Game <id>: ` or `Card <id>: ` identifiers are accepted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdPolicy {
+    /// 1, 2, 3... in order, as in the puzzle inputs.
+    #[default]
+    Sequential,
+    /// Any order and gaps, e.g. a reordered subset of an input.
+    Unique,
+    /// Anything, duplicates included.
+    Any,
+}
+
+/// Reads the `<kind> <id>: ` line headers, checking the IDs against an
+/// `IdPolicy`.
+pub(crate) struct Ids {
+    day: u8,
+    kind: &'static str,
+    policy: IdPolicy,
+    seen: HashSet<u32>,
+}
+
+impl Ids {
+    pub fn new(day: u8, kind: &'static str, policy: IdPolicy) -> Self {
+        Self {
+            day,
+            kind,
+            policy,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Returns the ID of `line` and the rest of it after the header.
+    pub fn header<'a>(
+        &mut self,
+        line_idx: usize,
+        line: &'a str,
+    ) -> Result<(u32, &'a str), ParseError> {
+        let error =
+            |token, message: String| ParseError::new(self.day, line_idx, line, token, message);
+
+        let Some((token, rest)) = line.split_once(": ").and_then(|(header, rest)| {
+            Some((header.strip_prefix(self.kind)?.strip_prefix(' ')?, rest))
+        }) else {
+            return Err(error(line, format!("Expected `{} <id>: `", self.kind)));
+        };
+
+        let token = token.trim_start();
+        let id: u32 = token
+            .parse()
+            .map_err(|_| error(token, "Invalid ID".to_string()))?;
+
+        let expected = self.seen.len() as u32 + 1;
+        match self.policy {
+            IdPolicy::Sequential if id != expected => {
+                return Err(error(token, format!("Expected {} {expected}", self.kind)));
+            }
+            IdPolicy::Sequential | IdPolicy::Unique if self.seen.contains(&id) => {
+                return Err(error(token, format!("Duplicate {} {id}", self.kind)));
+            }
+            _ => {}
+        }
+        self.seen.insert(id);
+
+        Ok((id, rest))
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Directory holding the per-user inputs, as `<dir>/<user>/dayNN.txt`.
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    User { dir: PathBuf, user: String },
+}
+
+impl InputSource {
+    /// The per-user source, using `AOC_INPUTS` and `AOC_USER` (or the login
+    /// name) when they are not given explicitly.
+    pub fn user(dir: Option<PathBuf>, user: Option<String>) -> Result<Self, InputError> {
+        let dir = dir
+            .or_else(|| std::env::var_os("AOC_INPUTS").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+
+        let user = user
+            .or_else(|| std::env::var("AOC_USER").ok())
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .ok_or(InputError::NoUser)?;
+
+        Ok(Self::User { dir, user })
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NoUser,
+    NotFound(PathBuf),
+    Io(String, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NoUser => write!(
+                f,
+                "No user to look up inputs for, set AOC_USER or use --user"
+            ),
+            InputError::NotFound(path) => write!(f, "Input file {} not found", path.display()),
+            InputError::Io(source, e) => write!(f, "Failed to read {source}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn user_path(dir: &Path, user: &str, day: u8) -> PathBuf {
+    dir.join(user).join(format!("day{day:02}.txt"))
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound(path.to_path_buf()),
+        _ => InputError::Io(path.display().to_string(), e),
+    })
+}
+
+/// Splits `input` into trimmed lines, accepting both `\n` and `\r\n` endings
+/// and ignoring blank lines at the start and end. Each line comes with its
+/// 0-based index in `input`, for error reporting.
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let end = input
+        .split('\n')
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .last()
+        .map_or(0, |(idx, _)| idx + 1);
+
+    input
+        .split('\n')
+        .map(str::trim)
+        .enumerate()
+        .take(end)
+        .skip_while(|(_, l)| l.is_empty())
+}
+
+/// Like `lines`, but only strips the `\r` of `\r\n` endings: spaces are kept,
+/// for inputs where columns matter. Empty lines at the start and end are
+/// still ignored.
+pub fn raw_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let lines = || {
+        input
+            .split('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l))
+            .enumerate()
+    };
+    let end = lines()
+        .filter(|(_, l)| !l.is_empty())
+        .last()
+        .map_or(0, |(idx, _)| idx + 1);
+
+    lines().take(end).skip_while(|(_, l)| l.is_empty())
+}
+
+/// Reads the input of `day` from `source`.
+pub fn load(source: &InputSource, day: u8) -> Result<String, InputError> {
+    match source {
+        InputSource::File(path) => read_file(path),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| InputError::Io("stdin".to_string(), e))?;
+            Ok(input)
+        }
+        InputSource::User { dir, user } => read_file(&user_path(dir, user, day)),
+    }
+}
+
+/// `input` as it could be saved: with a trailing newline, with Windows line
+/// endings, and with extra blank lines.
+#[cfg(test)]
+pub(crate) fn encodings(input: &str) -> Vec<String> {
+    let crlf = input.replace('\n', "\r\n");
+
+    vec![
+        input.to_string(),
+        format!("{input}\n"),
+        format!("\n{input}\n\n"),
+        crlf.clone(),
+        format!("{crlf}\r\n"),
+        format!("\r\n{crlf}\r\n\r\n"),
+    ]
+}
+
+/// The input the tests' expected answers were computed from, if checked out.
+#[cfg(test)]
+pub(crate) fn reference(day: u8) -> Option<String> {
+    const REFERENCE_USER: &str = "rofferom";
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_DIR);
+    match read_file(&user_path(&dir, REFERENCE_USER, day)) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("Skipping real input checks for day {day}: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalised_lines() {
+        let normalised: Vec<_> = lines("\r\n  a \r\n\r\nb\r\n\r\n\n").collect();
+        assert_eq!(normalised, [(1, "a"), (2, ""), (3, "b")]);
+
+        assert_eq!(lines("").count(), 0);
+        assert_eq!(lines("\n \r\n").count(), 0);
+
+        let raw: Vec<_> = raw_lines("\r\n  a \r\n\r\nb\r\n\r\n\n").collect();
+        assert_eq!(raw, [(1, "  a "), (2, ""), (3, "b")]);
+        assert_eq!(raw_lines("\n \r\n").collect::<Vec<_>>(), [(1, " ")]);
+    }
+}
@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+pub mod bench;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod error;
+pub mod grid;
+pub mod ids;
+pub mod input;
+pub mod json;
+pub mod registry;
+#[cfg(test)]
+mod testing;
+
+pub use error::ParseError;
+
+/// A puzzle solver: the input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    /// Fails when the input parsed but doesn't suit this part.
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, ParseError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, ParseError>;
+}
@@ -0,0 +1,123 @@
+use std::ops::RangeInclusive;
+
+use crate::bench::{self, Measurement};
+use crate::{day1, day2, day3, day4, day5, day6, ParseError};
+
+/// Solves one part from the raw input, formatting the answer.
+pub type Solver = fn(&str) -> Result<String, ParseError>;
+
+/// Benchmarks parsing and both parts over the given number of iterations.
+pub type Bencher = fn(&str, usize) -> Result<Vec<Measurement>, ParseError>;
+
+/// An alternative algorithm for one part, used to cross-check the main one.
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: Solver,
+}
+
+/// A registered day, the solvers for both parts and its benchmark.
+pub struct Day {
+    pub day: u8,
+    pub part1: Solver,
+    pub part2: Solver,
+    pub bench: Bencher,
+    pub variants: &'static [Variant],
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+
+    pub fn variant(&self, name: &str, part: u8) -> Option<Solver> {
+        self.variants
+            .iter()
+            .find(|v| v.name == name && v.part == part)
+            .map(|v| v.solve)
+    }
+}
+
+macro_rules! day {
+    ($day:literal, $module:ident, $solution:ident) => {
+        day!($day, $module, $solution, [])
+    };
+    ($day:literal, $module:ident, $solution:ident, [$($name:literal => $part:literal: $solve:ident),*]) => {
+        Day {
+            day: $day,
+            part1: |input| $module::solve_part1(input).map(|a| a.to_string()),
+            part2: |input| $module::solve_part2(input).map(|a| a.to_string()),
+            bench: bench::run::<$module::$solution>,
+            variants: &[$(Variant {
+                name: $name,
+                part: $part,
+                solve: |input| $module::$solve(input).map(|a| a.to_string()),
+            }),*],
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day1, Day1),
+    day!(2, day2, Day2),
+    day!(3, day3, Day3),
+    day!(4, day4, Day4),
+    day!(5, day5, Day5, [
+        "brute-force" => 2: solve_part2_brute_force,
+        "reverse-search" => 2: solve_part2_reverse_search
+    ]),
+    day!(6, day6, Day6),
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Parses a day selection: `all`, a single day (`5`) or a range (`1..4`,
+/// `1..=4`).
+pub fn parse_selection(s: &str) -> Option<RangeInclusive<u8>> {
+    if s == "all" {
+        let first = DAYS.first()?.day;
+        let last = DAYS.last()?.day;
+        return Some(first..=last);
+    }
+
+    if let Some((start, end)) = s.split_once("..=") {
+        return Some(start.parse().ok()?..=end.parse().ok()?);
+    }
+
+    if let Some((start, end)) = s.split_once("..") {
+        let end: u8 = end.parse().ok()?;
+        return Some(start.parse().ok()?..=end.checked_sub(1)?);
+    }
+
+    let day = s.parse().ok()?;
+    Some(day..=day)
+}
+
+/// Returns the registered days within `range`.
+pub fn select(range: &RangeInclusive<u8>) -> Vec<&'static Day> {
+    DAYS.iter().filter(|d| range.contains(&d.day)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection() {
+        assert_eq!(parse_selection("all"), Some(1..=6));
+        assert_eq!(parse_selection("5"), Some(5..=5));
+        assert_eq!(parse_selection("1..=4"), Some(1..=4));
+        assert_eq!(parse_selection("1..4"), Some(1..=3));
+        assert_eq!(parse_selection("1..0"), None);
+        assert_eq!(parse_selection("five"), None);
+
+        let days: Vec<_> = select(&(2..=4)).iter().map(|d| d.day).collect();
+        assert_eq!(days, [2, 3, 4]);
+    }
+}
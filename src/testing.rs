@@ -0,0 +1,11 @@
+/// Xorshift generator for randomised tests, seeded for reproducibility:
+/// each call returns a value below its bound.
+pub(crate) fn rng() -> impl FnMut(u64) -> u64 {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    move |bound| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    }
+}